@@ -0,0 +1,160 @@
+//! BLS signature checks and Merkle hashing.
+
+use crate::*;
+
+/// Prefix hashed into Merkle leaves so they can never be mistaken for inner nodes.
+pub const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+
+/// Prefix hashed into Merkle inner nodes.
+pub const MERKLE_NODE_PREFIX: &[u8] = &[1];
+
+/// The negated BN254 G2 generator in the encoding used by the `alt_bn128` pairing syscall.
+pub const NEG_G2_GENERATOR: [u8; 128] = [
+    0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
+    0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12, 0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3, 0x12, 0xc2,
+    0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76, 0x42, 0x6a, 0x00, 0x66, 0x5e, 0x5c, 0x44, 0x79,
+    0x67, 0x43, 0x22, 0xd4, 0xf7, 0x5e, 0xda, 0xdd, 0x46, 0xde, 0xbd, 0x5c, 0xd9, 0x92, 0xf6, 0xed,
+    0x27, 0x5d, 0xc4, 0xa2, 0x88, 0xd1, 0xaf, 0xb3, 0xcb, 0xb1, 0xac, 0x09, 0x18, 0x75, 0x24, 0xc7,
+    0xdb, 0x36, 0x39, 0x5d, 0xf7, 0xbe, 0x3b, 0x99, 0xe6, 0x73, 0xb1, 0x3a, 0x07, 0x5a, 0x65, 0xec,
+    0x1d, 0x9b, 0xef, 0xcd, 0x05, 0xa5, 0x32, 0x3e, 0x6d, 0xa4, 0xd4, 0x35, 0xf3, 0xb6, 0x17, 0xcd,
+    0xb3, 0xaf, 0x83, 0x28, 0x5c, 0x2d, 0xf7, 0x11, 0xef, 0x39, 0xc0, 0x15, 0x71, 0x82, 0x7f, 0x9d,
+];
+
+/// Verify an aggregated G1 signature against an aggregated G2 public key.
+pub(crate) fn check_signature(aggregated_g2_point: G2Point, signature: G1CompressedPoint, message: &[u8]) -> Result<()> {
+    aggregated_g2_point
+        .verify_signature::<Sha256Normalized, &[u8], G1CompressedPoint>(signature, message)
+        .map_err(|_| {
+            msg!("Signature verification failed.");
+            error!(ErrorCode::SignatureVerificationFailed)
+        })
+}
+
+/// Check that `pop_signature` is `g2_point_key` signing its own proof-of-possession message.
+/// Without it a rogue key derived from other members' keys could forge aggregate signatures.
+pub(crate) fn check_proof_of_possession(g2_point_key: &[u8; 128], pop_signature: [u8; 32]) -> Result<()> {
+    let message = proof_of_possession_message(g2_point_key);
+    check_signature(G2Point(*g2_point_key), G1CompressedPoint(pop_signature), &message)
+        .map_err(|_| error!(ErrorCode::InvalidProofOfPossession))
+}
+
+/// Emit `SignatureVerified` or `VerificationFailed` for the outcome of verifying `message`,
+/// passing the outcome through.
+pub(crate) fn record_verification<T>(
+    result: Result<T>,
+    message: &[u8],
+    committee: Option<Pubkey>,
+    signer_bitmap: Option<u32>,
+) -> Result<T> {
+    let message_hash = hash(message).to_bytes();
+    let slot = Clock::get()?.slot;
+    match &result {
+        Ok(_) => emit!(SignatureVerified { message_hash, committee, signer_bitmap, slot }),
+        Err(error) => emit!(VerificationFailed {
+            message_hash,
+            committee,
+            signer_bitmap,
+            slot,
+            reason: match error {
+                Error::AnchorError(error) => error.error_name.clone(),
+                Error::ProgramError(error) => error.program_error.to_string(),
+            },
+        }),
+    }
+    result
+}
+
+/// Sum a non-empty list of G2 public keys.
+pub(crate) fn aggregate_keys<'a>(mut keys: impl Iterator<Item = &'a [u8; 128]>) -> Result<G2Point> {
+    let first = keys.next().ok_or_else(|| error!(ErrorCode::NoOraclesProvided))?;
+    Ok(keys.fold(G2Point(*first), |aggregated, key| aggregated + G2Point(*key)))
+}
+
+/// Hash a Borsh-encoded `PriceReport` into a Merkle leaf.
+pub fn price_leaf(report: &[u8]) -> [u8; 32] {
+    hashv(&[MERKLE_LEAF_PREFIX, report]).to_bytes()
+}
+
+/// Recompute a Merkle root from one leaf and its sibling path.
+pub fn process_proof(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(leaf, |node, sibling| merkle_parent(&node, sibling))
+}
+
+/// Hash a committee member into a Merkle leaf.
+pub fn merkle_leaf(g2_point_key: &[u8; 128], weight: u64) -> [u8; 32] {
+    hashv(&[MERKLE_LEAF_PREFIX, g2_point_key, &weight.to_le_bytes()]).to_bytes()
+}
+
+/// Hash two Merkle nodes into their parent. The pair is sorted so proofs need no direction bits.
+pub fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[MERKLE_NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Recompute a Merkle root from several leaves and a multiproof. Leaves must be given in the order
+/// the tree consumes them; each flag says whether the next node comes from the leaves and hashes
+/// computed so far (`true`) or from `proof` (`false`).
+pub fn process_multiproof(leaves: &[[u8; 32]], proof: &[[u8; 32]], proof_flags: &[bool]) -> Result<[u8; 32]> {
+    let total_hashes = proof_flags.len();
+    require!(
+        leaves.len() + proof.len() == total_hashes + 1,
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let mut hashes: Vec<[u8; 32]> = Vec::with_capacity(total_hashes);
+    let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0usize, 0usize, 0usize);
+    let mut next_known = |hashes: &Vec<[u8; 32]>| -> Result<[u8; 32]> {
+        if leaf_pos < leaves.len() {
+            leaf_pos += 1;
+            Ok(leaves[leaf_pos - 1])
+        } else {
+            let node = hashes.get(hash_pos).copied().ok_or_else(|| error!(ErrorCode::InvalidMerkleProof))?;
+            hash_pos += 1;
+            Ok(node)
+        }
+    };
+    for flag in proof_flags {
+        let a = next_known(&hashes)?;
+        let b = if *flag {
+            next_known(&hashes)?
+        } else {
+            let node = proof.get(proof_pos).copied().ok_or_else(|| error!(ErrorCode::InvalidMerkleProof))?;
+            proof_pos += 1;
+            node
+        };
+        hashes.push(merkle_parent(&a, &b));
+    }
+    require!(proof_pos == proof.len(), ErrorCode::InvalidMerkleProof);
+
+    match (hashes.last(), leaves.first(), proof.first()) {
+        (Some(root), _, _) => Ok(*root),
+        (None, Some(leaf), _) => Ok(*leaf),
+        (None, None, Some(node)) => Ok(*node),
+        _ => err!(ErrorCode::InvalidMerkleProof),
+    }
+}
+
+/// Verify one aggregated G1 signature over distinct messages, where `messages[i]` was signed
+/// by the holder of `keys[i]`: checks e(H(m_1), pk_1) * ... * e(H(m_n), pk_n) * e(sig, -G2) == 1.
+pub(crate) fn check_distinct_signatures(keys: &[&[u8; 128]], messages: &[Vec<u8>], signature: G1CompressedPoint) -> Result<()> {
+    require!(!keys.is_empty() && keys.len() == messages.len(), ErrorCode::InvalidSignerBitmap);
+    let signature = G1Point::try_from(&signature).map_err(|_| error!(ErrorCode::DecompressionFailed))?;
+
+    let mut input = Vec::with_capacity((keys.len() + 1) * ALT_BN128_PAIRING_ELEMENT_LEN);
+    for (key, message) in keys.iter().zip(messages) {
+        let hashed = Sha256Normalized::try_hash_to_curve(message).map_err(|_| error!(ErrorCode::SignatureVerificationFailed))?;
+        input.extend_from_slice(&hashed.0);
+        input.extend_from_slice(*key);
+    }
+    input.extend_from_slice(&signature.0);
+    input.extend_from_slice(&NEG_G2_GENERATOR);
+
+    let result = alt_bn128_pairing(&input).map_err(|_| error!(ErrorCode::SignatureVerificationFailed))?;
+    let mut expected = [0u8; 32];
+    expected[31] = 1;
+    if result[..] != expected {
+        msg!("Signature verification failed.");
+        return err!(ErrorCode::SignatureVerificationFailed);
+    }
+    Ok(())
+}
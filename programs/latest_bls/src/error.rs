@@ -0,0 +1,150 @@
+//! Errors returned by the program.
+
+use crate::*;
+
+// Custom error codes for the program.
+#[error_code]
+pub enum ErrorCode {
+    #[msg("No oracles were provided in the accounts array.")]
+    NoOraclesProvided,
+    #[msg("Failed to decompress a G2 compressed public key.")]
+    DecompressionFailed,
+    #[msg("Failed to aggregate G2 public keys.")]
+    AggregationFailed,
+    #[msg("Signature verification failed.")]
+    SignatureVerificationFailed,
+    #[msg("Invalid oracle account provided.")]
+    InvalidOracleAccount,
+    #[msg("The signer is not the oracle's authority.")]
+    UnauthorizedAuthority,
+    #[msg("The signer is not the admin.")]
+    UnauthorizedAdmin,
+    #[msg("The program is paused.")]
+    ProgramPaused,
+    #[msg("The authority is not on the allowlist.")]
+    NotAllowlisted,
+    #[msg("The committee size must be greater than zero.")]
+    InvalidCommitteeSize,
+    #[msg("Too many oracles were provided for one committee.")]
+    CommitteeTooLarge,
+    #[msg("The same oracle was provided more than once.")]
+    DuplicateOracle,
+    #[msg("The oracle belongs to a different committee.")]
+    CommitteeMismatch,
+    #[msg("The threshold must be between one and the number of members.")]
+    InvalidThreshold,
+    #[msg("The signer bitmap selects members that do not exist.")]
+    InvalidSignerBitmap,
+    #[msg("Not enough committee members signed.")]
+    ThresholdNotMet,
+    #[msg("The committee has no member set for this epoch.")]
+    UnknownEpoch,
+    #[msg("The handoff window for the previous epoch has closed.")]
+    EpochExpired,
+    #[msg("Arithmetic overflow.")]
+    ArithmeticOverflow,
+    #[msg("The snapshot is still within its retention period.")]
+    SnapshotRetained,
+    #[msg("The Merkle proof does not match the committee root.")]
+    InvalidMerkleProof,
+    #[msg("The write exceeds the proof buffer capacity.")]
+    ProofBufferOverflow,
+    #[msg("The member weight must be greater than zero.")]
+    InvalidWeight,
+    #[msg("The registry page is full.")]
+    PageFull,
+    #[msg("The member index is invalid, out of order or not active.")]
+    InvalidMemberIndex,
+    #[msg("The page does not belong to this registry.")]
+    InvalidRegistryPage,
+    #[msg("The message does not match the session's message hash.")]
+    MessageMismatch,
+    #[msg("The session has already been verified.")]
+    SessionAlreadyVerified,
+    #[msg("The registry changed since the session started.")]
+    RegistryChanged,
+    #[msg("The report could not be decoded.")]
+    InvalidReport,
+    #[msg("The report is for a different feed.")]
+    FeedMismatch,
+    #[msg("The report is not newer than the stored one.")]
+    StaleReport,
+    #[msg("Timestamp limits must not be negative.")]
+    InvalidTimestampLimits,
+    #[msg("The price or message is older than the allowed staleness.")]
+    StalePrice,
+    #[msg("The timestamp is too far ahead of the cluster clock.")]
+    FutureTimestamp,
+    #[msg("The price history does not cover the requested window.")]
+    InsufficientHistory,
+    #[msg("The price neither deviated enough nor reached the heartbeat interval.")]
+    UpdateNotNeeded,
+    #[msg("The feed is not in the feed registry.")]
+    UnknownFeed,
+    #[msg("The feed has been retired.")]
+    FeedRetired,
+    #[msg("The feed registry is full.")]
+    FeedRegistryFull,
+    #[msg("The bond is below the required minimum or the bond accounts are missing.")]
+    InsufficientBond,
+    #[msg("The oracle still has bonded or unbonding tokens.")]
+    BondOutstanding,
+    #[msg("Too many unbond requests are pending.")]
+    UnbondingQueueFull,
+    #[msg("No unbond request has reached its release slot.")]
+    UnbondingPending,
+    #[msg("The token account does not hold the bond token.")]
+    InvalidBondAccount,
+    #[msg("The oracle is jailed.")]
+    OracleJailed,
+    #[msg("The reports are not for the same feed and round or do not differ.")]
+    NotEquivocation,
+    #[msg("There are no rewards to claim.")]
+    NothingToClaim,
+    #[msg("Commission cannot exceed 10000 basis points.")]
+    InvalidCommission,
+    #[msg("Optimistic reports are disabled.")]
+    OptimisticDisabled,
+    #[msg("The dispute window of this report has closed.")]
+    DisputeWindowClosed,
+    #[msg("The dispute window of this report is still open.")]
+    DisputeWindowOpen,
+    #[msg("The feed is frozen pending review.")]
+    FeedFrozen,
+    #[msg("The committee is frozen pending review.")]
+    CommitteeFrozen,
+    #[msg("Not frozen.")]
+    NotFrozen,
+    #[msg("A feed with this id is already registered.")]
+    FeedExists,
+    #[msg("The proposal nonce is not the next governance nonce.")]
+    InvalidNonce,
+    #[msg("The governance proposal has expired.")]
+    ProposalExpired,
+    #[msg("The destination account does not match the transfer intent.")]
+    InvalidDestination,
+    #[msg("The vault cannot cover the transfer and stay rent exempt.")]
+    InsufficientVaultBalance,
+    #[msg("A vault token account of the intent's mint is required.")]
+    InvalidVaultTokenAccount,
+    #[msg("The deposit is for another chain.")]
+    InvalidChain,
+    #[msg("The amount must be positive.")]
+    InvalidAmount,
+    #[msg("The mint is not the bridge's wrapped token.")]
+    InvalidMint,
+    #[msg("The proof of possession does not match the public key.")]
+    InvalidProofOfPossession,
+    #[msg("The bond cannot drop below the minimum while the oracle serves in a committee.")]
+    BondLockedInCommittee,
+    #[msg("The oracle does not accept delegations.")]
+    DelegationsClosed,
+    #[msg("The optimistic bond and dispute window must both be greater than zero.")]
+    InvalidOptimisticParameters,
+    #[msg("The root was attested before the committee or feed was last frozen.")]
+    AttestedBeforeFreeze,
+    #[msg("Once a governance committee is set this change must go through governance.")]
+    GovernedByCommittee,
+    #[msg("Every rotation member needs a signed weight no greater than its bond plus delegations.")]
+    WeightAboveStake,
+}
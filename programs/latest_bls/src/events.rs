@@ -0,0 +1,159 @@
+//! Events emitted by the program.
+
+use crate::*;
+
+/// Emitted when an oracle registers its G2 public key.
+#[event]
+pub struct OracleRegistered {
+    pub oracle: Pubkey,
+    pub authority: Pubkey,
+    pub g2_point_key: [u8; 128], // Registered G2 public key
+}
+
+/// Emitted when an oracle is deregistered.
+#[event]
+pub struct OracleDeregistered {
+    pub oracle: Pubkey,
+    pub authority: Pubkey,
+    pub committee: Option<Pubkey>, // Committee the oracle was removed from, if any
+}
+
+/// Emitted when a committee is created or rotated to a new set of oracles.
+#[event]
+pub struct OracleRotated {
+    pub committee: Pubkey,
+    pub epoch: u64,           // Epoch the oracles serve from
+    pub threshold: u16,       // Signers required in that epoch
+    pub oracles: Vec<Pubkey>, // Oracle accounts in member order
+}
+
+/// Emitted when a relayer posts an optimistic report.
+#[event]
+pub struct OptimisticReportPosted {
+    pub feed: Pubkey,
+    pub poster: Pubkey,
+    pub report: PriceReport,
+    pub final_slot: u64,     // First slot the report can be finalized
+}
+
+/// Emitted when an optimistic report is disputed and its bond paid to the challenger.
+#[event]
+pub struct OptimisticReportDisputed {
+    pub feed: Pubkey,
+    pub poster: Pubkey,
+    pub challenger: Pubkey,
+    pub round_id: u64,
+    pub bond: u64,          // Lamports forfeited
+}
+
+/// Emitted when a committee is caught signing two different reports for the same round.
+#[event]
+pub struct CommitteeEquivocated {
+    pub committee: Pubkey,
+    pub reporter: Pubkey,
+    pub feed_id: [u8; 32],
+    pub round_id: u64,
+    pub committee_frozen: bool,
+}
+
+/// Emitted when a governance action is executed.
+#[event]
+pub struct GovernanceExecuted {
+    pub committee: Pubkey,
+    pub nonce: u64,
+    pub action: GovernanceAction,
+}
+
+/// Emitted when a committee vault transfer is executed.
+#[event]
+pub struct VaultTransferExecuted {
+    pub vault: Pubkey,        // Vault spent from
+    pub destination: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+/// Emitted when a bridged deposit is claimed and its wrapped tokens minted.
+#[event]
+pub struct BridgeDepositClaimed {
+    pub bridge: Pubkey,
+    pub tx_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+/// Emitted when wrapped tokens are burned to be released on the source chain.
+#[event]
+pub struct BridgeBurned {
+    pub bridge: Pubkey,
+    pub destination_chain_id: u64,
+    pub sender: Pubkey,
+    pub recipient: [u8; 32],
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+/// Emitted when an oracle is slashed for equivocation.
+#[event]
+pub struct OracleSlashed {
+    pub oracle: Pubkey,
+    pub reporter: Pubkey,
+    pub feed_id: [u8; 32],
+    pub round_id: u64,
+    pub amount: u64,
+    pub reward: u64,
+}
+
+/// Emitted when an authority is added to or removed from the allowlist.
+#[event]
+pub struct AllowlistUpdated {
+    pub authority: Pubkey,
+    pub allowed: bool,
+}
+
+/// Emitted when a Merkle committee's member tree is set.
+#[event]
+pub struct MerkleCommitteeUpdated {
+    pub merkle_committee: Pubkey,
+    pub root: [u8; 32],
+    pub member_count: u32,
+    pub quorum_weight: u64,       // Signing weight required
+}
+
+/// Emitted when a paged registry member is appended or removed.
+#[event]
+pub struct RegistryMemberUpdated {
+    pub registry: Pubkey,
+    pub index: u32,
+    pub g2_point_key: [u8; 128], // Member's G2 public key
+    pub weight: u64,
+    pub active: bool,
+}
+
+/// Emitted when a feed is created, its policy changes or it is retired.
+#[event]
+pub struct FeedPolicyUpdated {
+    pub policy: FeedPolicy,
+}
+
+/// Emitted when an aggregated signature is verified.
+#[event]
+pub struct SignatureVerified {
+    pub message_hash: [u8; 32],
+    pub committee: Option<Pubkey>, // Committee, registry or snapshot's committee, if any
+    pub signer_bitmap: Option<u32>,
+    pub slot: u64,                 // Slot of the verification
+}
+
+/// Emitted when an aggregated signature is rejected, just before the instruction fails. It only
+/// appears in the logs of the failed transaction, so indexers must read failed transactions to see it.
+#[event]
+pub struct VerificationFailed {
+    pub message_hash: [u8; 32],
+    pub committee: Option<Pubkey>, // Committee, registry or snapshot's committee, if any
+    pub signer_bitmap: Option<u32>,
+    pub slot: u64,                 // Slot of the attempt
+    pub reason: String,
+}
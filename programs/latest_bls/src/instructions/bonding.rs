@@ -0,0 +1,289 @@
+//! Oracle bonds, unbonding and slashing.
+
+use crate::*;
+
+/// Move `amount` bond tokens from an authority's token account into the bond vault.
+pub(crate) fn deposit_bond<'info>(
+    from: &Account<'info, TokenAccount>,
+    bond_vault: &Account<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: bond_vault.to_account_info(),
+                authority: authority.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Move `amount` tokens out of the bond vault, signing as the vault PDA.
+pub(crate) fn release_bond<'info>(
+    config: &Account<'info, ProgramConfig>,
+    bond_vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let config_key = config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[BOND_VAULT_SEED, config_key.as_ref(), &[config.bond_vault_bump]]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: bond_vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: bond_vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+/// Decode two reports and check they conflict: same feed and round, different contents.
+/// Returns the first report.
+pub fn check_equivocation(report_a: &[u8], report_b: &[u8]) -> Result<PriceReport> {
+    let a = PriceReport::try_from_slice(report_a).map_err(|_| error!(ErrorCode::InvalidReport))?;
+    let b = PriceReport::try_from_slice(report_b).map_err(|_| error!(ErrorCode::InvalidReport))?;
+    require!(
+        a.feed_id == b.feed_id && a.round_id == b.round_id && a != b,
+        ErrorCode::NotEquivocation
+    );
+    Ok(a)
+}
+
+/// Accounts structure for enabling bonding.
+#[derive(Accounts)]
+pub struct ConfigureBonding<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub bond_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [BOND_VAULT_SEED, config.key().as_ref()],
+        bump,
+        token::mint = bond_mint,
+        token::authority = bond_vault
+    )]
+    pub bond_vault: Account<'info, TokenAccount>, // Program-owned vault holding every bond
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the vault
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for adding to or withdrawing from an oracle's bond.
+#[derive(Accounts)]
+pub struct ManageBond<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    pub authority: Signer<'info>,
+    #[account(mut, constraint = authority_token_account.mint == bond_vault.mint @ ErrorCode::InvalidBondAccount)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [BOND_VAULT_SEED, config.key().as_ref()], bump = config.bond_vault_bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts structure for setting the slashing parameters.
+#[derive(Accounts)]
+pub struct SetSlashingParameters<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(constraint = Some(treasury.mint) == config.bond_mint @ ErrorCode::InvalidBondAccount)]
+    pub treasury: Account<'info, TokenAccount>, // Receives the non-reporter share of slashed bonds
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for slashing an equivocating oracle.
+#[derive(Accounts)]
+pub struct SlashEquivocation<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), oracle.authority.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(mut)]
+    pub committee: Option<Account<'info, Committee>>, // Required when the oracle belongs to a committee
+    #[account(mut, seeds = [BOND_VAULT_SEED, config.key().as_ref()], bump = config.bond_vault_bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(mut, constraint = reporter_token_account.mint == bond_vault.mint @ ErrorCode::InvalidBondAccount)]
+    pub reporter_token_account: Account<'info, TokenAccount>, // Receives the reporter's reward
+    #[account(mut, constraint = Some(treasury.key()) == config.treasury @ ErrorCode::InvalidBondAccount)]
+    pub treasury: Account<'info, TokenAccount>, // Receives the rest of the slashed amount
+    pub reporter: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts structure for queueing an unbond request.
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    pub authority: Signer<'info>,
+    pub committee: Option<Account<'info, Committee>>, // Required when the oracle belongs to a committee
+}
+
+pub(crate) fn handle_configure_bonding(
+    ctx: Context<ConfigureBonding>,
+    min_bond: u64,
+    unbonding_delay_slots: u64,
+) -> Result<()> {
+    require!(min_bond > 0, ErrorCode::InsufficientBond);
+    let config = &mut ctx.accounts.config;
+    config.bond_mint = Some(ctx.accounts.bond_mint.key());
+    config.min_bond = min_bond;
+    config.unbonding_delay_slots = unbonding_delay_slots;
+    config.bond_vault_bump = ctx.bumps.bond_vault;
+    msg!("Bonding enabled with a minimum of {} tokens", min_bond);
+    Ok(())
+}
+
+pub(crate) fn handle_bond(ctx: Context<ManageBond>, amount: u64) -> Result<()> {
+    let accounts = &ctx.accounts;
+    deposit_bond(
+        &accounts.authority_token_account,
+        &accounts.bond_vault,
+        &accounts.authority,
+        &accounts.token_program,
+        amount,
+    )?;
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.bonded = oracle
+        .bonded
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    msg!("Oracle {} bonded {} tokens", oracle.key(), amount);
+    Ok(())
+}
+
+pub(crate) fn handle_request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.oracle.jailed, ErrorCode::OracleJailed);
+    let slot = Clock::get()?.slot;
+    let release_slot = slot.saturating_add(ctx.accounts.config.unbonding_delay_slots);
+    let oracle_key = ctx.accounts.oracle.key();
+    let seated = match ctx.accounts.oracle.committee {
+        Some(bound) => {
+            let committee = ctx.accounts.committee.as_ref().ok_or_else(|| error!(ErrorCode::CommitteeMismatch))?;
+            require_keys_eq!(committee.key(), bound, ErrorCode::CommitteeMismatch);
+            committee.seats(&oracle_key, slot)
+        }
+        None => false,
+    };
+    let min_bond = ctx.accounts.config.min_bond;
+    let oracle = &mut ctx.accounts.oracle;
+    require!(amount > 0 && amount <= oracle.bonded, ErrorCode::InsufficientBond);
+    require!(!seated || oracle.bonded - amount >= min_bond, ErrorCode::BondLockedInCommittee);
+    require!(oracle.unbonding.len() < MAX_UNBONDING_REQUESTS, ErrorCode::UnbondingQueueFull);
+    oracle.bonded -= amount;
+    oracle.unbonding.push(UnbondingRequest { amount, release_slot });
+    msg!("Oracle {} unbonding {} tokens until slot {}", oracle.key(), amount, release_slot);
+    Ok(())
+}
+
+pub(crate) fn handle_withdraw_unbonded(ctx: Context<ManageBond>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let oracle = &mut ctx.accounts.oracle;
+    require!(!oracle.jailed, ErrorCode::OracleJailed);
+    let (released, pending): (Vec<UnbondingRequest>, Vec<UnbondingRequest>) =
+        oracle.unbonding.iter().partition(|request| request.release_slot <= slot);
+    let amount = released.iter().try_fold(0u64, |total, request| total.checked_add(request.amount));
+    let amount = amount.ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    require!(amount > 0, ErrorCode::UnbondingPending);
+    oracle.unbonding = pending;
+
+    let accounts = &ctx.accounts;
+    release_bond(
+        &accounts.config,
+        &accounts.bond_vault,
+        &accounts.authority_token_account,
+        &accounts.token_program,
+        amount,
+    )?;
+    msg!("Withdrew {} unbonded tokens", amount);
+    Ok(())
+}
+
+pub(crate) fn handle_set_slashing_parameters(
+    ctx: Context<SetSlashingParameters>,
+    slash_bps: u16,
+    reporter_reward_bps: u16,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(slash_bps <= 10_000 && reporter_reward_bps <= 10_000, ErrorCode::InvalidThreshold);
+    let config = &mut ctx.accounts.config;
+    config.slash_bps = slash_bps;
+    config.reporter_reward_bps = reporter_reward_bps;
+    config.treasury = Some(ctx.accounts.treasury.key());
+    Ok(())
+}
+
+pub(crate) fn handle_slash_equivocation(
+    ctx: Context<SlashEquivocation>,
+    report_a: Vec<u8>,
+    signature_a: [u8; 32],
+    report_b: Vec<u8>,
+    signature_b: [u8; 32],
+) -> Result<()> {
+    let conflict = check_equivocation(&report_a, &report_b)?;
+    let oracle_key = ctx.accounts.oracle.key();
+    let oracle = &mut ctx.accounts.oracle;
+    require!(!oracle.jailed, ErrorCode::OracleJailed);
+    let g2_point_key = G2Point(oracle.g2_point_key);
+    check_signature(g2_point_key, G1CompressedPoint(signature_a), &price_report_message(&report_a))?;
+    check_signature(g2_point_key, G1CompressedPoint(signature_b), &price_report_message(&report_b))?;
+
+    let config = &ctx.accounts.config;
+    let amount = oracle.slash(config.slash_bps)?;
+    oracle.jailed = true;
+    remove_from_committee(&oracle_key, oracle.committee, ctx.accounts.committee.as_mut())?;
+
+    let reward = (u128::from(amount) * u128::from(config.reporter_reward_bps) / 10_000) as u64;
+    let accounts = &ctx.accounts;
+    release_bond(config, &accounts.bond_vault, &accounts.reporter_token_account, &accounts.token_program, reward)?;
+    release_bond(config, &accounts.bond_vault, &accounts.treasury, &accounts.token_program, amount - reward)?;
+
+    emit!(OracleSlashed {
+        oracle: oracle_key,
+        reporter: accounts.reporter.key(),
+        feed_id: conflict.feed_id,
+        round_id: conflict.round_id,
+        amount,
+        reward,
+    });
+    msg!("Oracle {} slashed {} tokens and jailed", oracle_key, amount);
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! Bridge deposits attested by a committee, and burns back to the source chain.
+
+use crate::*;
+
+/// Accounts structure for creating a bridge and its wrapped token.
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, decimals: u8)]
+pub struct InitializeBridge<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Bridge::LEN,
+        seeds = [BRIDGE_SEED, committee.key().as_ref(), &source_chain_id.to_le_bytes()],
+        bump
+    )]
+    pub bridge: Account<'info, Bridge>, // Bridge PDA, mint authority of the wrapped token
+    #[account(
+        init,
+        payer = admin,
+        seeds = [WRAPPED_MINT_SEED, bridge.key().as_ref()],
+        bump,
+        mint::decimals = decimals,
+        mint::authority = bridge
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the bridge
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for claiming a bridged deposit.
+#[derive(Accounts)]
+#[instruction(epoch: u64, signer_bitmap: u32, aggregated_and_compressed_g1_signature: [u8; 32], deposit: DepositAttestation)]
+pub struct Claim<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        seeds = [BRIDGE_SEED, committee.key().as_ref(), &bridge.source_chain_id.to_le_bytes()],
+        bump = bridge.bump,
+        has_one = mint @ ErrorCode::InvalidMint
+    )]
+    pub bridge: Account<'info, Bridge>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ConsumedDeposit::LEN,
+        seeds = [DEPOSIT_SEED, bridge.key().as_ref(), &deposit.tx_hash, &deposit.nonce.to_le_bytes()],
+        bump
+    )]
+    pub consumed_deposit: Account<'info, ConsumedDeposit>,
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = recipient_token_account.owner == deposit.recipient @ ErrorCode::InvalidDestination
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>, // Pays for the consumed deposit marker
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for burning wrapped tokens back to the source chain.
+#[derive(Accounts)]
+pub struct BurnForBridge<'info> {
+    #[account(mut, has_one = mint @ ErrorCode::InvalidMint)]
+    pub bridge: Account<'info, Bridge>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(mut, token::mint = mint)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handle_initialize_bridge(
+    ctx: Context<InitializeBridge>,
+    source_chain_id: u64,
+    decimals: u8,
+) -> Result<()> {
+    let bridge = &mut ctx.accounts.bridge;
+    bridge.committee = ctx.accounts.committee.key();
+    bridge.source_chain_id = source_chain_id;
+    bridge.mint = ctx.accounts.mint.key();
+    bridge.outbound_nonce = 0;
+    bridge.bump = ctx.bumps.bridge;
+    msg!("Bridge {} from chain {} with {} decimals", bridge.key(), source_chain_id, decimals);
+    Ok(())
+}
+
+pub(crate) fn handle_claim(
+    ctx: Context<Claim>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    deposit: DepositAttestation,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let bridge = &ctx.accounts.bridge;
+    require!(deposit.amount > 0, ErrorCode::InvalidAmount);
+    require!(deposit.source_chain_id == bridge.source_chain_id, ErrorCode::InvalidChain);
+    let message = bridge_deposit_message(&bridge.key(), &deposit)?;
+    let committee = &ctx.accounts.committee;
+    let result = committee.verify(epoch, slot, signer_bitmap, aggregated_and_compressed_g1_signature, &message);
+    record_verification(result, &message, Some(committee.key()), Some(signer_bitmap))?;
+
+    let consumed_deposit = &mut ctx.accounts.consumed_deposit;
+    consumed_deposit.bridge = bridge.key();
+    consumed_deposit.tx_hash = deposit.tx_hash;
+    consumed_deposit.nonce = deposit.nonce;
+    consumed_deposit.claimed_slot = slot;
+    consumed_deposit.bump = ctx.bumps.consumed_deposit;
+
+    let committee_key = bridge.committee;
+    let chain_id = bridge.source_chain_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[BRIDGE_SEED, committee_key.as_ref(), &chain_id, &[bridge.bump]]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: bridge.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        deposit.amount,
+    )?;
+    emit!(BridgeDepositClaimed {
+        bridge: bridge.key(),
+        tx_hash: deposit.tx_hash,
+        recipient: deposit.recipient,
+        amount: deposit.amount,
+        nonce: deposit.nonce,
+    });
+    Ok(())
+}
+
+pub(crate) fn handle_burn_for_bridge(ctx: Context<BurnForBridge>, amount: u64, recipient: [u8; 32]) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    let bridge = &mut ctx.accounts.bridge;
+    let nonce = bridge.outbound_nonce;
+    bridge.outbound_nonce = nonce.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    emit!(BridgeBurned {
+        bridge: bridge.key(),
+        destination_chain_id: bridge.source_chain_id,
+        sender: ctx.accounts.owner.key(),
+        recipient,
+        amount,
+        nonce,
+    });
+    Ok(())
+}
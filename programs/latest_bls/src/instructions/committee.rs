@@ -0,0 +1,427 @@
+//! Committee formation, rotation, snapshots and verification.
+
+use crate::*;
+
+/// Load the oracle accounts that make up a committee for `epoch`, bind each of them to
+/// `committee` and checkpoint its weight: the given one, or its current stake without `weights`.
+/// Every account must be writable, unique and not already bound to another committee.
+fn collect_members(
+    accounts: &[AccountInfo],
+    config: &Account<ProgramConfig>,
+    committee: &Pubkey,
+    epoch: u64,
+    weights: Option<&[u64]>,
+) -> Result<Vec<CommitteeMember>> {
+    let max_members = MAX_COMMITTEE_MEMBERS.min(config.max_committee_size as usize);
+    require!(accounts.len() <= max_members, ErrorCode::CommitteeTooLarge);
+    if let Some(weights) = weights {
+        require!(weights.len() == accounts.len(), ErrorCode::WeightAboveStake);
+    }
+    let config_key = config.key();
+
+    let mut members: Vec<CommitteeMember> = Vec::with_capacity(accounts.len());
+    for (index, account_info) in accounts.iter().enumerate() {
+        require!(
+            members.iter().all(|member| member.oracle != account_info.key()),
+            ErrorCode::DuplicateOracle
+        );
+        require!(account_info.is_writable, ErrorCode::InvalidOracleAccount);
+        let mut oracle = load_oracle(account_info, &config_key)?;
+        if config.bond_mint.is_some() {
+            require!(oracle.bonded >= config.min_bond, ErrorCode::InsufficientBond);
+        }
+        match oracle.committee {
+            Some(existing) => require_keys_eq!(existing, *committee, ErrorCode::CommitteeMismatch),
+            None => oracle.committee = Some(*committee),
+        }
+        let weight = oracle.checkpoint_weight(epoch, weights.map(|weights| weights[index]))?;
+        let mut data = account_info.try_borrow_mut_data()?;
+        oracle.try_serialize(&mut &mut data[..])?;
+        members.push(CommitteeMember {
+            oracle: account_info.key(),
+            g2_point_key: oracle.g2_point_key,
+            weight,
+        });
+    }
+    Ok(members)
+}
+
+/// Remove `oracle` from the committee it is bound to, if any. That committee must be supplied.
+/// A committee left with fewer members than its threshold can no longer verify.
+pub(crate) fn remove_from_committee(
+    oracle: &Pubkey,
+    bound_committee: Option<Pubkey>,
+    committee: Option<&mut Account<Committee>>,
+) -> Result<()> {
+    let Some(committee_key) = bound_committee else {
+        return Ok(());
+    };
+    let committee = committee.ok_or_else(|| error!(ErrorCode::CommitteeMismatch))?;
+    require_keys_eq!(committee.key(), committee_key, ErrorCode::CommitteeMismatch);
+    committee.current.remove_member(oracle);
+    if let Some(previous) = committee.previous.as_mut() {
+        previous.remove_member(oracle);
+    }
+    msg!("Oracle {} removed from committee {}", oracle, committee_key);
+    Ok(())
+}
+
+/// Load an `OracleAccount` from a remaining account, rejecting accounts not owned by this program
+/// or not at the oracle PDA of `config` for the stored authority.
+pub(crate) fn load_oracle(account_info: &AccountInfo, config: &Pubkey) -> Result<OracleAccount> {
+    require_keys_eq!(*account_info.owner, crate::ID, ErrorCode::InvalidOracleAccount);
+    let data = account_info.try_borrow_data()?;
+    let oracle = OracleAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(ErrorCode::InvalidOracleAccount))?;
+    let expected = Pubkey::create_program_address(
+        &[ORACLE_SEED, config.as_ref(), oracle.authority.as_ref(), &[oracle.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidOracleAccount))?;
+    require_keys_eq!(expected, account_info.key(), ErrorCode::InvalidOracleAccount);
+    require!(!oracle.jailed, ErrorCode::OracleJailed);
+    Ok(oracle)
+}
+
+/// Accounts structure for creating a committee.
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct InitializeCommittee<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Committee::LEN,
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee_id.to_le_bytes()],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the committee
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for rotating a committee to its next epoch.
+#[derive(Accounts)]
+pub struct RotateCommittee<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+}
+
+/// Accounts structure for admin-only committee changes.
+#[derive(Accounts)]
+pub struct UpdateCommittee<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for snapshotting a committee.
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SnapshotCommittee<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CommitteeSnapshot::space(committee.set_to_snapshot(epoch).map_or(0, |set| set.members.len())),
+        seeds = [SNAPSHOT_SEED, committee.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub snapshot: Account<'info, CommitteeSnapshot>,
+    #[account(mut)]
+    pub payer: Signer<'info>, // Pays for the snapshot, receives the rent on close
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for closing an expired snapshot.
+#[derive(Accounts)]
+pub struct CloseSnapshot<'info> {
+    #[account(
+        seeds = [COMMITTEE_SEED, committee.config.as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        mut,
+        seeds = [SNAPSHOT_SEED, committee.key().as_ref(), &snapshot.set.epoch.to_le_bytes()],
+        bump = snapshot.bump,
+        has_one = rent_payer,
+        close = rent_payer
+    )]
+    pub snapshot: Account<'info, CommitteeSnapshot>,
+    /// CHECK: Only receives the rent refund, checked against the snapshot
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+/// Accounts structure for verifying a signature against a snapshot.
+#[derive(Accounts)]
+pub struct VerifySnapshotSignature<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [SNAPSHOT_SEED, snapshot.committee.as_ref(), &snapshot.set.epoch.to_le_bytes()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, CommitteeSnapshot>,
+    #[account(address = snapshot.committee @ ErrorCode::CommitteeMismatch)]
+    pub committee: Account<'info, Committee>,
+}
+
+/// Accounts structure for verifying a committee signature.
+#[derive(Accounts)]
+pub struct VerifyCommitteeSignature<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+}
+
+pub(crate) fn handle_set_weight_quorum(ctx: Context<UpdateCommittee>, quorum_bps: u16) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(quorum_bps <= 10_000, ErrorCode::InvalidThreshold);
+    ctx.accounts.committee.current.quorum_bps = quorum_bps;
+    Ok(())
+}
+
+pub(crate) fn handle_initialize_committee(
+    ctx: Context<InitializeCommittee>,
+    committee_id: u64,
+    threshold: u16,
+    handoff_window_slots: u64,
+    snapshot_retention_epochs: u64,
+) -> Result<()> {
+    let committee_key = ctx.accounts.committee.key();
+    let members = collect_members(
+        ctx.remaining_accounts,
+        &ctx.accounts.config,
+        &committee_key,
+        0,
+        None,
+    )?;
+
+    let committee = &mut ctx.accounts.committee;
+    committee.config = ctx.accounts.config.key();
+    committee.committee_id = committee_id;
+    committee.current = CommitteeSet::new(0, threshold, members)?;
+    committee.previous = None;
+    committee.previous_valid_until = 0;
+    committee.handoff_window_slots = handoff_window_slots;
+    committee.snapshot_retention_epochs = snapshot_retention_epochs;
+    committee.max_staleness = 0;
+    committee.max_future_skew = 0;
+    committee.frozen = false;
+    committee.frozen_slot = 0;
+    committee.bump = ctx.bumps.committee;
+    emit!(OracleRotated {
+        committee: committee_key,
+        epoch: 0,
+        threshold,
+        oracles: committee.current.members.iter().map(|member| member.oracle).collect(),
+    });
+    msg!("Committee {} initialized at epoch 0", committee_key);
+    Ok(())
+}
+
+pub(crate) fn handle_rotate_committee(
+    ctx: Context<RotateCommittee>,
+    threshold: u16,
+    weights: Vec<u64>,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+) -> Result<()> {
+    let committee_key = ctx.accounts.committee.key();
+    let next_epoch = ctx
+        .accounts
+        .committee
+        .current
+        .epoch
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    let members = collect_members(
+        ctx.remaining_accounts,
+        &ctx.accounts.config,
+        &committee_key,
+        next_epoch,
+        Some(&weights),
+    )?;
+
+    let committee = &mut ctx.accounts.committee;
+    let mut next = CommitteeSet::new(next_epoch, threshold, members)?;
+    next.quorum_bps = committee.current.quorum_bps;
+    let message = rotation_message(&committee_key, next_epoch, threshold, &next.members);
+    let slot = Clock::get()?.slot;
+    let result = committee.verify(
+        committee.current.epoch,
+        slot,
+        signer_bitmap,
+        aggregated_and_compressed_g1_signature,
+        &message,
+    );
+    record_verification(result, &message, Some(committee_key), Some(signer_bitmap))?;
+
+    emit!(OracleRotated {
+        committee: committee_key,
+        epoch: next_epoch,
+        threshold,
+        oracles: next.members.iter().map(|member| member.oracle).collect(),
+    });
+    committee.previous = Some(std::mem::replace(&mut committee.current, next));
+    committee.previous_valid_until = slot.saturating_add(committee.handoff_window_slots);
+    msg!("Committee {} rotated to epoch {}", committee_key, next_epoch);
+    Ok(())
+}
+
+pub(crate) fn handle_set_handoff_window(ctx: Context<UpdateCommittee>, handoff_window_slots: u64) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    ctx.accounts.committee.handoff_window_slots = handoff_window_slots;
+    Ok(())
+}
+
+pub(crate) fn handle_set_timestamp_limits(
+    ctx: Context<UpdateCommittee>,
+    max_staleness: i64,
+    max_future_skew: i64,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(max_staleness >= 0 && max_future_skew >= 0, ErrorCode::InvalidTimestampLimits);
+    let committee = &mut ctx.accounts.committee;
+    committee.max_staleness = max_staleness;
+    committee.max_future_skew = max_future_skew;
+    Ok(())
+}
+
+pub(crate) fn handle_verify_timestamped_signature(
+    ctx: Context<VerifyCommitteeSignature>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    envelope: Vec<u8>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let committee = &ctx.accounts.committee;
+    let result = committee
+        .verify(epoch, clock.slot, signer_bitmap, aggregated_and_compressed_g1_signature, &timestamped_message(&envelope))
+        .and_then(|signer_count| {
+            let decoded =
+                TimestampedMessage::try_from_slice(&envelope).map_err(|_| error!(ErrorCode::InvalidReport))?;
+            check_timestamp(decoded.timestamp, clock.unix_timestamp, committee.max_staleness, committee.max_future_skew)?;
+            Ok(signer_count)
+        });
+    record_verification(result, &envelope, Some(committee.key()), Some(signer_bitmap))?;
+
+    msg!("✅ Aggregated signature verified successfully!");
+    Ok(())
+}
+
+pub(crate) fn handle_set_snapshot_retention(
+    ctx: Context<UpdateCommittee>,
+    snapshot_retention_epochs: u64,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    ctx.accounts.committee.snapshot_retention_epochs = snapshot_retention_epochs;
+    Ok(())
+}
+
+pub(crate) fn handle_snapshot_committee(ctx: Context<SnapshotCommittee>, epoch: u64) -> Result<()> {
+    let committee = &ctx.accounts.committee;
+    let set = committee
+        .set_to_snapshot(epoch)
+        .ok_or_else(|| error!(ErrorCode::UnknownEpoch))?
+        .clone();
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.committee = committee.key();
+    snapshot.set = set;
+    snapshot.created_slot = Clock::get()?.slot;
+    snapshot.rent_payer = ctx.accounts.payer.key();
+    snapshot.bump = ctx.bumps.snapshot;
+    msg!("Snapshot of committee {} taken for epoch {}", committee.key(), epoch);
+    Ok(())
+}
+
+pub(crate) fn handle_close_snapshot(ctx: Context<CloseSnapshot>) -> Result<()> {
+    let committee = &ctx.accounts.committee;
+    let retained_until = ctx
+        .accounts
+        .snapshot
+        .set
+        .epoch
+        .saturating_add(committee.snapshot_retention_epochs);
+    require!(committee.current.epoch > retained_until, ErrorCode::SnapshotRetained);
+    msg!("Snapshot for epoch {} closed", ctx.accounts.snapshot.set.epoch);
+    Ok(())
+}
+
+pub(crate) fn handle_verify_snapshot_signature(
+    ctx: Context<VerifySnapshotSignature>,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    message: Vec<u8>,
+) -> Result<()> {
+    let snapshot = &ctx.accounts.snapshot;
+    let result = ctx.accounts.committee.verify_set(
+        &snapshot.set,
+        signer_bitmap,
+        aggregated_and_compressed_g1_signature,
+        &application_message(&message),
+    );
+    record_verification(result, &message, Some(snapshot.committee), Some(signer_bitmap))?;
+
+    msg!("✅ Aggregated signature verified successfully!");
+    Ok(())
+}
+
+pub(crate) fn handle_verify_committee_signature(
+    ctx: Context<VerifyCommitteeSignature>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    message: Vec<u8>,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let committee = &ctx.accounts.committee;
+    let signed = application_message(&message);
+    let result = committee.verify(epoch, slot, signer_bitmap, aggregated_and_compressed_g1_signature, &signed);
+    record_verification(result, &message, Some(committee.key()), Some(signer_bitmap))?;
+
+    msg!("✅ Aggregated signature verified successfully!");
+    Ok(())
+}
+
+pub(crate) fn handle_unfreeze_committee(ctx: Context<UpdateCommittee>) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let committee = &mut ctx.accounts.committee;
+    require!(committee.frozen, ErrorCode::NotFrozen);
+    committee.frozen = false;
+    msg!("Committee {} unfrozen", committee.key());
+    Ok(())
+}
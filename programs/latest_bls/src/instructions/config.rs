@@ -0,0 +1,180 @@
+//! Program configuration, admin transfer and the registration allowlist.
+
+use crate::*;
+
+/// Accounts structure for creating the program configuration.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(init, payer = admin, space = 8 + ProgramConfig::LEN, seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Initial admin, pays for the account
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>, // Program data account; its upgrade authority must sign
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for admin-only configuration changes.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for accepting a pending admin transfer.
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(pending_admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub pending_admin: Signer<'info>,
+}
+
+/// Accounts structure for adding an authority to the allowlist.
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct AddToAllowlist<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowlistEntry::LEN,
+        seeds = [ALLOWLIST_SEED, authority.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the entry
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for removing an authority from the allowlist.
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [ALLOWLIST_SEED, allowlist_entry.authority.as_ref()],
+        bump = allowlist_entry.bump,
+        close = admin
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, receives the rent refund
+}
+
+pub(crate) fn handle_initialize_config(
+    ctx: Context<InitializeConfig>,
+    registration_mode: RegistrationMode,
+    max_committee_size: u16,
+) -> Result<()> {
+    require!(max_committee_size > 0, ErrorCode::InvalidCommitteeSize);
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.pending_admin = None;
+    config.registration_mode = registration_mode;
+    config.max_committee_size = max_committee_size;
+    config.paused = false;
+    config.bond_mint = None;
+    config.min_bond = 0;
+    config.unbonding_delay_slots = 0;
+    config.bond_vault_bump = 0;
+    config.slash_bps = 0;
+    config.reporter_reward_bps = 0;
+    config.treasury = None;
+    config.optimistic_bond = 0;
+    config.dispute_window_slots = 0;
+    config.governance_committee = None;
+    config.governance_nonce = 0;
+    config.bump = ctx.bumps.config;
+    msg!("Config initialized with admin {}", config.admin);
+    Ok(())
+}
+
+pub(crate) fn handle_update_config(
+    ctx: Context<UpdateConfig>,
+    registration_mode: RegistrationMode,
+    max_committee_size: u16,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(max_committee_size > 0, ErrorCode::InvalidCommitteeSize);
+    let config = &mut ctx.accounts.config;
+    config.registration_mode = registration_mode;
+    config.max_committee_size = max_committee_size;
+    Ok(())
+}
+
+pub(crate) fn handle_set_bond_parameters(
+    ctx: Context<UpdateConfig>,
+    min_bond: u64,
+    unbonding_delay_slots: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.check_admin_governs()?;
+    require!(config.bond_mint.is_some() && min_bond > 0, ErrorCode::InsufficientBond);
+    config.min_bond = min_bond;
+    config.unbonding_delay_slots = unbonding_delay_slots;
+    Ok(())
+}
+
+pub(crate) fn handle_set_optimistic_parameters(
+    ctx: Context<UpdateConfig>,
+    optimistic_bond: u64,
+    dispute_window_slots: u64,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(optimistic_bond > 0 && dispute_window_slots > 0, ErrorCode::InvalidOptimisticParameters);
+    let config = &mut ctx.accounts.config;
+    config.optimistic_bond = optimistic_bond;
+    config.dispute_window_slots = dispute_window_slots;
+    Ok(())
+}
+
+pub(crate) fn handle_set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    ctx.accounts.config.paused = paused;
+    msg!("Program paused: {}", paused);
+    Ok(())
+}
+
+pub(crate) fn handle_propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    ctx.accounts.config.pending_admin = Some(new_admin);
+    msg!("Admin transfer proposed to {}", new_admin);
+    Ok(())
+}
+
+pub(crate) fn handle_accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.pending_admin.key();
+    config.pending_admin = None;
+    msg!("Admin transferred to {}", config.admin);
+    Ok(())
+}
+
+pub(crate) fn handle_add_to_allowlist(ctx: Context<AddToAllowlist>, authority: Pubkey) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.authority = authority;
+    entry.bump = ctx.bumps.allowlist_entry;
+    emit!(AllowlistUpdated { authority, allowed: true });
+    Ok(())
+}
+
+pub(crate) fn handle_remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    emit!(AllowlistUpdated { authority: ctx.accounts.allowlist_entry.authority, allowed: false });
+    Ok(())
+}
@@ -0,0 +1,266 @@
+//! Token delegations to oracles and their rewards.
+
+use crate::*;
+
+/// Credit an oracle's pooled rewards to it, start the delegation earning on tokens a checkpoint
+/// now counts, then pay the delegation its pending share out of the reward pool. Returns the
+/// amount paid; the caller resets the delegation's reward debt.
+fn settle_delegation<'info>(
+    reward_pool: &mut Account<'info, RewardPool>,
+    oracle: &mut Account<'info, OracleAccount>,
+    delegation: &mut Account<'info, Delegation>,
+    delegator: &AccountInfo<'info>,
+) -> Result<u64> {
+    let pooled = reward_pool.take_balance(&oracle.key());
+    oracle.accrue_rewards(pooled)?;
+    let earned = delegation.pending_rewards(oracle.reward_per_share)?;
+    let amount = earned
+        .checked_add(delegation.activate_pending(oracle)?)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    if amount > 0 {
+        reward_pool.sub_lamports(amount)?;
+        delegator.add_lamports(amount)?;
+        msg!("Paid {} lamports of rewards to delegator {}", amount, delegator.key());
+    }
+    Ok(amount)
+}
+
+/// Settle a delegation, then move `amount` of its tokens, pending ones first, into its unbonding
+/// queue until `release_slot`.
+fn queue_undelegation<'info>(
+    reward_pool: &mut Account<'info, RewardPool>,
+    oracle: &mut Account<'info, OracleAccount>,
+    delegation: &mut Account<'info, Delegation>,
+    delegator: &AccountInfo<'info>,
+    amount: u64,
+    release_slot: u64,
+) -> Result<()> {
+    require!(delegation.unbonding.len() < MAX_UNBONDING_REQUESTS, ErrorCode::UnbondingQueueFull);
+    settle_delegation(reward_pool, oracle, delegation, delegator)?;
+    require!(
+        amount > 0 && u128::from(amount) <= u128::from(delegation.amount) + u128::from(delegation.pending),
+        ErrorCode::InsufficientBond
+    );
+    let from_pending = amount.min(delegation.pending);
+    delegation.pending -= from_pending;
+    if from_pending > 0 && delegation.pending == 0 {
+        oracle.pending_delegations = oracle
+            .pending_delegations
+            .checked_sub(1)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    }
+    delegation.amount -= amount - from_pending;
+    oracle.pending_delegated -= from_pending;
+    oracle.delegated -= amount;
+    delegation.unbonding.push(UnbondingRequest { amount, release_slot });
+    delegation.reset_reward_debt(oracle.reward_per_share)?;
+    msg!("Undelegating {} tokens from oracle {} until slot {}", amount, oracle.key(), release_slot);
+    Ok(())
+}
+
+/// Accounts structure for delegating tokens to an oracle.
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused,
+        constraint = config.bond_mint.is_some() @ ErrorCode::InvalidBondAccount
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), oracle.authority.as_ref()],
+        bump = oracle.bump,
+        constraint = !oracle.jailed @ ErrorCode::OracleJailed,
+        constraint = oracle.accepts_delegations @ ErrorCode::DelegationsClosed
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = 8 + Delegation::LEN,
+        seeds = [DELEGATION_SEED, oracle.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    #[account(mut, constraint = delegator_token_account.mint == bond_vault.mint @ ErrorCode::InvalidBondAccount)]
+    pub delegator_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [BOND_VAULT_SEED, config.key().as_ref()], bump = config.bond_vault_bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for undelegating from an oracle or claiming delegation rewards.
+#[derive(Accounts)]
+pub struct ManageDelegation<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), oracle.authority.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, oracle.key().as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut)]
+    pub delegator: Signer<'info>, // Owner of the delegation, receives the rewards
+}
+
+/// Accounts structure for an oracle's authority releasing a delegation.
+#[derive(Accounts)]
+pub struct ReleaseDelegation<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, oracle.key().as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut)]
+    pub delegator: SystemAccount<'info>, // Owner of the delegation, receives the rewards
+    pub authority: Signer<'info>,
+}
+
+/// Accounts structure for withdrawing undelegated tokens.
+#[derive(Accounts)]
+pub struct WithdrawUndelegated<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, delegation.oracle.as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub delegation: Account<'info, Delegation>, // Delegation PDA, closed once empty
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    #[account(mut, constraint = delegator_token_account.mint == bond_vault.mint @ ErrorCode::InvalidBondAccount)]
+    pub delegator_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [BOND_VAULT_SEED, config.key().as_ref()], bump = config.bond_vault_bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handle_delegate(ctx: Context<Delegate>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InsufficientBond);
+    let accounts = &ctx.accounts;
+    deposit_bond(
+        &accounts.delegator_token_account,
+        &accounts.bond_vault,
+        &accounts.delegator,
+        &accounts.token_program,
+        amount,
+    )?;
+
+    let accounts = ctx.accounts;
+    let delegation = &mut accounts.delegation;
+    if delegation.oracle == Pubkey::default() {
+        delegation.oracle = accounts.oracle.key();
+        delegation.delegator = accounts.delegator.key();
+        delegation.bump = ctx.bumps.delegation;
+    }
+    let delegator = accounts.delegator.to_account_info();
+    settle_delegation(&mut accounts.reward_pool, &mut accounts.oracle, delegation, &delegator)?;
+    let oracle = &mut accounts.oracle;
+    if delegation.pending == 0 {
+        oracle.pending_delegations = oracle
+            .pending_delegations
+            .checked_add(1)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    }
+    delegation.pending = delegation
+        .pending
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    delegation.pending_epoch = oracle.weight_checkpoints.last().map_or(0, |checkpoint| checkpoint.epoch + 1);
+    oracle.delegated = oracle
+        .delegated
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    oracle.pending_delegated = oracle
+        .pending_delegated
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    delegation.reset_reward_debt(oracle.reward_per_share)?;
+    msg!("Delegated {} tokens to oracle {}", amount, oracle.key());
+    Ok(())
+}
+
+pub(crate) fn handle_undelegate(ctx: Context<ManageDelegation>, amount: u64) -> Result<()> {
+    let release_slot = Clock::get()?.slot.saturating_add(ctx.accounts.config.unbonding_delay_slots);
+    let accounts = ctx.accounts;
+    let delegator = accounts.delegator.to_account_info();
+    queue_undelegation(&mut accounts.reward_pool, &mut accounts.oracle, &mut accounts.delegation, &delegator, amount, release_slot)
+}
+
+pub(crate) fn handle_release_delegation(ctx: Context<ReleaseDelegation>) -> Result<()> {
+    require!(!ctx.accounts.oracle.jailed, ErrorCode::OracleJailed);
+    let release_slot = Clock::get()?.slot.saturating_add(ctx.accounts.config.unbonding_delay_slots);
+    let accounts = ctx.accounts;
+    let amount = accounts
+        .delegation
+        .amount
+        .checked_add(accounts.delegation.pending)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    let delegator = accounts.delegator.to_account_info();
+    queue_undelegation(&mut accounts.reward_pool, &mut accounts.oracle, &mut accounts.delegation, &delegator, amount, release_slot)
+}
+
+pub(crate) fn handle_claim_delegation_rewards(ctx: Context<ManageDelegation>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let delegation = &mut accounts.delegation;
+    let delegator = accounts.delegator.to_account_info();
+    let amount = settle_delegation(&mut accounts.reward_pool, &mut accounts.oracle, delegation, &delegator)?;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    delegation.reset_reward_debt(accounts.oracle.reward_per_share)?;
+    Ok(())
+}
+
+pub(crate) fn handle_withdraw_undelegated(ctx: Context<WithdrawUndelegated>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let delegation = &mut ctx.accounts.delegation;
+    let (released, pending): (Vec<UnbondingRequest>, Vec<UnbondingRequest>) =
+        delegation.unbonding.iter().partition(|request| request.release_slot <= slot);
+    let amount = released.iter().try_fold(0u64, |total, request| total.checked_add(request.amount));
+    let amount = amount.ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    require!(amount > 0, ErrorCode::UnbondingPending);
+    delegation.unbonding = pending;
+
+    let accounts = &ctx.accounts;
+    release_bond(
+        &accounts.config,
+        &accounts.bond_vault,
+        &accounts.delegator_token_account,
+        &accounts.token_program,
+        amount,
+    )?;
+    if accounts.delegation.amount == 0 && accounts.delegation.pending == 0 && accounts.delegation.unbonding.is_empty() {
+        accounts.delegation.close(accounts.delegator.to_account_info())?;
+    }
+    msg!("Withdrew {} undelegated tokens", amount);
+    Ok(())
+}
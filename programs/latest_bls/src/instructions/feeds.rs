@@ -0,0 +1,567 @@
+//! Price feeds, their policies and committee-signed updates.
+
+use crate::*;
+
+/// Check a committee-signed `report` against the committee's limits and the feed's registered
+/// policy and store it as the feed's latest price.
+fn apply_report(
+    committee: &Committee,
+    policy: &FeedPolicy,
+    feed: &mut PriceFeed,
+    history: &AccountLoader<PriceHistory>,
+    report: PriceReport,
+    signer_count: u16,
+    clock: &Clock,
+) -> Result<()> {
+    policy.check_report(committee, &report, clock.unix_timestamp)?;
+    policy.check_update(&feed.latest, &report, signer_count)?;
+
+    feed.latest = report;
+    feed.min_price = report.price;
+    feed.max_price = report.price;
+    feed.signer_count = signer_count;
+    feed.last_update_slot = clock.slot;
+    history.load_mut()?.push(&feed.latest, clock.slot);
+    msg!(
+        "Price feed updated: price {} x 10^{} ± {} at round {}",
+        feed.latest.price,
+        feed.latest.expo,
+        feed.latest.conf,
+        feed.latest.round_id
+    );
+    Ok(())
+}
+
+/// Reject `timestamp` if it is more than `max_staleness` seconds before `now` or more than
+/// `max_future_skew` seconds after it. A limit of 0 disables that check.
+pub fn check_timestamp(timestamp: i64, now: i64, max_staleness: i64, max_future_skew: i64) -> Result<()> {
+    if max_staleness > 0 {
+        require!(timestamp >= now.saturating_sub(max_staleness), ErrorCode::StalePrice);
+    }
+    if max_future_skew > 0 {
+        require!(timestamp <= now.saturating_add(max_future_skew), ErrorCode::FutureTimestamp);
+    }
+    Ok(())
+}
+
+/// Sort `values` in place and return their median, averaging the middle pair for an even count.
+pub fn median(values: &mut [i64]) -> i64 {
+    values.sort_unstable();
+    let middle = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[middle]
+    } else {
+        ((i128::from(values[middle - 1]) + i128::from(values[middle])) / 2) as i64
+    }
+}
+
+/// Initialize a newly created feed and its history.
+fn init_price_feed(
+    feed: &mut Account<PriceFeed>,
+    history: &AccountLoader<PriceHistory>,
+    config: Pubkey,
+    feed_id: [u8; 32],
+    bump: u8,
+) -> Result<()> {
+    feed.config = config;
+    feed.feed_id = feed_id;
+    feed.latest = PriceReport { feed_id, ..PriceReport::default() };
+    feed.last_update_slot = 0;
+    feed.optimistic = PriceReport { feed_id, ..PriceReport::default() };
+    feed.optimistic_update_slot = 0;
+    feed.bump = bump;
+
+    let mut history = history.load_init()?;
+    history.feed = feed.key();
+    msg!("Price feed {} created", feed.key());
+    Ok(())
+}
+
+/// Accounts structure for creating a price feed.
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct CreatePriceFeed<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(mut, seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PriceFeed::LEN,
+        seeds = [FEED_SEED, config.key().as_ref(), &feed_id],
+        bump
+    )]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<PriceHistory>(),
+        seeds = [HISTORY_SEED, feed.key().as_ref()],
+        bump
+    )]
+    pub history: AccountLoader<'info, PriceHistory>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the feed
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for creating the accounts of a feed registered by governance.
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct OpenPriceFeed<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceFeed::LEN,
+        seeds = [FEED_SEED, config.key().as_ref(), &feed_id],
+        bump
+    )]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<PriceHistory>(),
+        seeds = [HISTORY_SEED, feed.key().as_ref()],
+        bump
+    )]
+    pub history: AccountLoader<'info, PriceHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>, // Pays for the feed
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for creating the feed registry.
+#[derive(Accounts)]
+pub struct InitializeFeedRegistry<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeedRegistry::LEN,
+        seeds = [FEED_REGISTRY_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the registry
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for changing a feed's policy.
+#[derive(Accounts)]
+pub struct UpdateFeedPolicy<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for retiring a feed.
+#[derive(Accounts)]
+pub struct RetireFeed<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for unfreezing a feed.
+#[derive(Accounts)]
+pub struct UnfreezeFeed<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for reporting two conflicting committee-signed reports.
+#[derive(Accounts)]
+pub struct ReportCommitteeEquivocation<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(mut, seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    pub reporter: Signer<'info>,
+}
+
+/// Accounts structure for updating a price feed.
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(mut, seeds = [FEED_SEED, config.key().as_ref(), &feed.feed_id], bump = feed.bump)]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(mut, seeds = [HISTORY_SEED, feed.key().as_ref()], bump)]
+    pub history: AccountLoader<'info, PriceHistory>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Option<Account<'info, RewardPool>>, // Pays the signers, if the program has a reward pool
+}
+
+/// Accounts structure for attesting a root over a batch of price reports.
+#[derive(Accounts)]
+#[instruction(epoch: u64, signer_bitmap: u32, aggregated_and_compressed_g1_signature: [u8; 32], root: [u8; 32])]
+pub struct AttestPriceRoot<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AttestedRoot::LEN,
+        seeds = [ATTESTED_ROOT_SEED, committee.key().as_ref(), &root],
+        bump
+    )]
+    pub attested_root: Account<'info, AttestedRoot>,
+    #[account(mut)]
+    pub payer: Signer<'info>, // Pays for the attested root
+    pub system_program: Program<'info, System>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Option<Account<'info, RewardPool>>, // Pays the signers, if the program has a reward pool
+}
+
+/// Accounts structure for updating a price feed from an attested root.
+#[derive(Accounts)]
+pub struct UpdatePriceFromRoot<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        seeds = [ATTESTED_ROOT_SEED, committee.key().as_ref(), &attested_root.root],
+        bump = attested_root.bump,
+        has_one = committee @ ErrorCode::CommitteeMismatch
+    )]
+    pub attested_root: Account<'info, AttestedRoot>,
+    #[account(seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(mut, seeds = [FEED_SEED, config.key().as_ref(), &feed.feed_id], bump = feed.bump)]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(mut, seeds = [HISTORY_SEED, feed.key().as_ref()], bump)]
+    pub history: AccountLoader<'info, PriceHistory>,
+}
+
+/// Accounts structure for closing an attested root.
+#[derive(Accounts)]
+pub struct CloseAttestedRoot<'info> {
+    #[account(mut, has_one = rent_payer, close = rent_payer)]
+    pub attested_root: Account<'info, AttestedRoot>,
+    #[account(mut)]
+    pub rent_payer: Signer<'info>, // Paid for the root, receives the refund
+}
+
+/// Accounts structure for reading a feed's price history.
+#[derive(Accounts)]
+pub struct ReadPriceHistory<'info> {
+    #[account(seeds = [FEED_SEED, feed.config.as_ref(), &feed.feed_id], bump = feed.bump)]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(seeds = [FEED_REGISTRY_SEED, feed.config.as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(seeds = [HISTORY_SEED, feed.key().as_ref()], bump)]
+    pub history: AccountLoader<'info, PriceHistory>,
+}
+
+pub(crate) fn handle_initialize_feed_registry(ctx: Context<InitializeFeedRegistry>) -> Result<()> {
+    let feed_registry = &mut ctx.accounts.feed_registry;
+    feed_registry.config = ctx.accounts.config.key();
+    feed_registry.feeds = Vec::new();
+    feed_registry.bump = ctx.bumps.feed_registry;
+    Ok(())
+}
+
+pub(crate) fn handle_create_price_feed(
+    ctx: Context<CreatePriceFeed>,
+    feed_id: [u8; 32],
+    settings: FeedSettings,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    settings.validate()?;
+    let policy = FeedPolicy {
+        feed_id,
+        committee: ctx.accounts.committee.key(),
+        settings,
+        retired: false,
+        frozen: false,
+        frozen_slot: 0,
+    };
+    ctx.accounts.feed_registry.register(policy)?;
+    emit!(FeedPolicyUpdated { policy });
+    let config = ctx.accounts.config.key();
+    init_price_feed(&mut ctx.accounts.feed, &ctx.accounts.history, config, feed_id, ctx.bumps.feed)
+}
+
+pub(crate) fn handle_open_price_feed(ctx: Context<OpenPriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+    let policy = ctx.accounts.feed_registry.policy_mut(&feed_id)?;
+    require!(!policy.retired, ErrorCode::FeedRetired);
+    let config = ctx.accounts.config.key();
+    init_price_feed(&mut ctx.accounts.feed, &ctx.accounts.history, config, feed_id, ctx.bumps.feed)
+}
+
+pub(crate) fn handle_update_feed_policy(
+    ctx: Context<UpdateFeedPolicy>,
+    feed_id: [u8; 32],
+    settings: FeedSettings,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    settings.validate()?;
+    let committee = ctx.accounts.committee.key();
+    let policy = ctx.accounts.feed_registry.policy_mut(&feed_id)?;
+    require!(!policy.retired, ErrorCode::FeedRetired);
+    policy.committee = committee;
+    policy.settings = settings;
+    emit!(FeedPolicyUpdated { policy: *policy });
+    Ok(())
+}
+
+pub(crate) fn handle_retire_feed(ctx: Context<RetireFeed>, feed_id: [u8; 32]) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let policy = ctx.accounts.feed_registry.policy_mut(&feed_id)?;
+    policy.retired = true;
+    emit!(FeedPolicyUpdated { policy: *policy });
+    msg!("Price feed retired");
+    Ok(())
+}
+
+pub(crate) fn handle_report_committee_equivocation(
+    ctx: Context<ReportCommitteeEquivocation>,
+    report_a: CommitteeSignedReport,
+    report_b: CommitteeSignedReport,
+    freeze_committee: bool,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let committee = &ctx.accounts.committee;
+    for signed in [&report_a, &report_b] {
+        committee.verify(
+            signed.epoch,
+            slot,
+            signed.signer_bitmap,
+            signed.aggregated_and_compressed_g1_signature,
+            &price_report_message(&signed.report),
+        )?;
+    }
+    let report = check_equivocation(&report_a.report, &report_b.report)?;
+
+    let committee_key = committee.key();
+    let feed_registry = &mut ctx.accounts.feed_registry;
+    let policy = feed_registry.policy_mut(&report.feed_id)?;
+    require_keys_eq!(policy.committee, committee_key, ErrorCode::CommitteeMismatch);
+    for policy in feed_registry.feeds.iter_mut() {
+        let affected = if freeze_committee {
+            policy.committee == committee_key
+        } else {
+            policy.feed_id == report.feed_id
+        };
+        if affected {
+            // Roots attested up to now may carry the equivocated reports
+            policy.frozen_slot = slot;
+            if !policy.frozen {
+                policy.frozen = true;
+                emit!(FeedPolicyUpdated { policy: *policy });
+            }
+        }
+    }
+    if freeze_committee {
+        ctx.accounts.committee.frozen = true;
+        ctx.accounts.committee.frozen_slot = slot;
+    }
+    emit!(CommitteeEquivocated {
+        committee: committee_key,
+        reporter: ctx.accounts.reporter.key(),
+        feed_id: report.feed_id,
+        round_id: report.round_id,
+        committee_frozen: freeze_committee,
+    });
+    msg!("Committee {} equivocated on round {}", committee_key, report.round_id);
+    Ok(())
+}
+
+pub(crate) fn handle_unfreeze_feed(ctx: Context<UnfreezeFeed>, feed_id: [u8; 32]) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let policy = ctx.accounts.feed_registry.policy_mut(&feed_id)?;
+    require!(policy.frozen, ErrorCode::NotFrozen);
+    policy.frozen = false;
+    emit!(FeedPolicyUpdated { policy: *policy });
+    msg!("Price feed unfrozen");
+    Ok(())
+}
+
+pub(crate) fn handle_update_price(
+    ctx: Context<UpdatePrice>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    report: Vec<u8>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let committee = &ctx.accounts.committee;
+    let message = price_report_message(&report);
+    let result = committee
+        .verify(epoch, clock.slot, signer_bitmap, aggregated_and_compressed_g1_signature, &message);
+    let signer_count = record_verification(result, &report, Some(committee.key()), Some(signer_bitmap))?;
+
+    let report = PriceReport::try_from_slice(&report).map_err(|_| error!(ErrorCode::InvalidReport))?;
+    let accounts = ctx.accounts;
+    let policy = accounts.feed_registry.policy(&accounts.feed.feed_id, &accounts.committee.key())?;
+    apply_report(&accounts.committee, policy, &mut accounts.feed, &accounts.history, report, signer_count, &clock)?;
+    credit_signers(accounts.reward_pool.as_mut(), &accounts.committee, epoch, signer_bitmap, clock.slot)
+}
+
+pub(crate) fn handle_attest_price_root(
+    ctx: Context<AttestPriceRoot>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    root: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let committee = &ctx.accounts.committee;
+    let message = price_root_message(&committee.key(), &root);
+    let result = committee
+        .verify(epoch, clock.slot, signer_bitmap, aggregated_and_compressed_g1_signature, &message);
+    let signer_count = record_verification(result, &message, Some(committee.key()), Some(signer_bitmap))?;
+
+    let attested_root = &mut ctx.accounts.attested_root;
+    attested_root.committee = committee.key();
+    attested_root.root = root;
+    attested_root.epoch = epoch;
+    attested_root.signer_count = signer_count;
+    attested_root.attested_slot = clock.slot;
+    attested_root.rent_payer = ctx.accounts.payer.key();
+    attested_root.bump = ctx.bumps.attested_root;
+    msg!("Price root attested by {} members", signer_count);
+    credit_signers(ctx.accounts.reward_pool.as_mut(), committee, epoch, signer_bitmap, clock.slot)
+}
+
+pub(crate) fn handle_update_price_from_root(
+    ctx: Context<UpdatePriceFromRoot>,
+    report: Vec<u8>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let attested_root = &ctx.accounts.attested_root;
+    require!(
+        process_proof(price_leaf(&report), &proof) == attested_root.root,
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let report = PriceReport::try_from_slice(&report).map_err(|_| error!(ErrorCode::InvalidReport))?;
+    let (signer_count, attested_slot) = (attested_root.signer_count, attested_root.attested_slot);
+    let accounts = ctx.accounts;
+    accounts.committee.check_attested_at(attested_slot)?;
+    let policy = accounts.feed_registry.policy(&accounts.feed.feed_id, &accounts.committee.key())?;
+    require!(attested_slot > policy.frozen_slot, ErrorCode::AttestedBeforeFreeze);
+    apply_report(&accounts.committee, policy, &mut accounts.feed, &accounts.history, report, signer_count, &clock)
+}
+
+pub(crate) fn handle_update_price_median(
+    ctx: Context<UpdatePrice>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    observations: Vec<Vec<u8>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let committee = &ctx.accounts.committee;
+    let result = committee.set_for_epoch(epoch, clock.slot).and_then(|set| {
+        set.verify_distinct(signer_bitmap, aggregated_and_compressed_g1_signature, &observations)
+    });
+    // Events carry the hash of the observations concatenated in signer order
+    let signer_count =
+        record_verification(result, &observations.concat(), Some(committee.key()), Some(signer_bitmap))?;
+
+    let reports = observations
+        .iter()
+        .map(|observation| PriceReport::try_from_slice(observation).map_err(|_| error!(ErrorCode::InvalidReport)))
+        .collect::<Result<Vec<_>>>()?;
+    let feed = &mut ctx.accounts.feed;
+    let policy = ctx.accounts.feed_registry.policy(&feed.feed_id, &committee.key())?;
+    let first = reports[0];
+    for report in &reports {
+        require!(report.round_id == first.round_id, ErrorCode::InvalidReport);
+        policy.check_report(committee, report, clock.unix_timestamp)?;
+    }
+
+    let mut prices: Vec<i64> = reports.iter().map(|report| report.price).collect();
+    let mut confs = reports
+        .iter()
+        .map(|report| i64::try_from(report.conf).map_err(|_| error!(ErrorCode::InvalidReport)))
+        .collect::<Result<Vec<i64>>>()?;
+    // The aggregate is only as fresh as its oldest observation
+    let publish_time = reports.iter().map(|report| report.publish_time).min().unwrap_or(first.publish_time);
+    let aggregate = PriceReport {
+        feed_id: feed.feed_id,
+        price: median(&mut prices),
+        expo: first.expo,
+        conf: u64::try_from(median(&mut confs)).map_err(|_| error!(ErrorCode::InvalidReport))?,
+        publish_time,
+        round_id: first.round_id,
+    };
+    policy.check_update(&feed.latest, &aggregate, signer_count)?;
+
+    feed.latest = aggregate;
+    feed.min_price = prices[0]; // Sorted by `median`
+    feed.max_price = prices[prices.len() - 1];
+    feed.signer_count = signer_count;
+    feed.last_update_slot = clock.slot;
+    ctx.accounts.history.load_mut()?.push(&feed.latest, clock.slot);
+    msg!(
+        "Price feed updated: median {} x 10^{} in [{}, {}] from {} oracles at round {}",
+        feed.latest.price,
+        feed.latest.expo,
+        feed.min_price,
+        feed.max_price,
+        feed.signer_count,
+        feed.latest.round_id
+    );
+    credit_signers(ctx.accounts.reward_pool.as_mut(), &ctx.accounts.committee, epoch, signer_bitmap, clock.slot)
+}
+
+pub(crate) fn handle_get_twap(ctx: Context<ReadPriceHistory>, window: i64) -> Result<i64> {
+    ctx.accounts.feed_registry.active_policy(&ctx.accounts.feed.feed_id)?;
+    let twap = ctx.accounts.history.load()?.twap(Clock::get()?.unix_timestamp, window)?;
+    msg!("TWAP over {}s: {}", window, twap);
+    Ok(twap)
+}
@@ -0,0 +1,185 @@
+//! Configuration changes authorized by a governance committee.
+
+use crate::*;
+
+/// The committee a governance action applies to: the governance committee itself, or the
+/// `target_committee` account when it is another one.
+fn governance_target<'a, 'info>(
+    governance: &'a mut Account<'info, Committee>,
+    target_committee: &'a mut Option<Account<'info, Committee>>,
+    committee: &Pubkey,
+) -> Result<&'a mut Account<'info, Committee>> {
+    if governance.key() == *committee {
+        return Ok(governance);
+    }
+    target_committee
+        .as_mut()
+        .filter(|target| target.key() == *committee)
+        .ok_or_else(|| error!(ErrorCode::CommitteeMismatch))
+}
+
+/// The feed registry, which feed actions require.
+fn governance_registry<'a, 'info>(
+    feed_registry: &'a mut Option<Account<'info, FeedRegistry>>,
+) -> Result<&'a mut Account<'info, FeedRegistry>> {
+    feed_registry.as_mut().ok_or_else(|| error!(ErrorCode::UnknownFeed))
+}
+
+/// Accounts structure for choosing the governance committee.
+#[derive(Accounts)]
+pub struct SetGovernanceCommittee<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for executing a governance action.
+#[derive(Accounts)]
+pub struct ExecuteGovernance<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump,
+        constraint = config.governance_committee == Some(committee.key()) @ ErrorCode::CommitteeMismatch
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        mut,
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &target_committee.committee_id.to_le_bytes()],
+        bump = target_committee.bump
+    )]
+    pub target_committee: Option<Account<'info, Committee>>, // Committee a committee action applies to, if not the governance committee
+    #[account(mut, seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>, // Required by feed actions
+}
+
+pub(crate) fn handle_set_governance_committee(ctx: Context<SetGovernanceCommittee>) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let committee = ctx.accounts.committee.key();
+    ctx.accounts.config.governance_committee = Some(committee);
+    msg!("Governance committee set to {}", committee);
+    Ok(())
+}
+
+pub(crate) fn handle_execute_governance(
+    ctx: Context<ExecuteGovernance>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    proposal: GovernanceProposal,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let config_key = ctx.accounts.config.key();
+    let committee_key = ctx.accounts.committee.key();
+    require!(proposal.nonce == ctx.accounts.config.governance_nonce, ErrorCode::InvalidNonce);
+    require!(slot <= proposal.expiry_slot, ErrorCode::ProposalExpired);
+    let message = governance_message(&config_key, &committee_key, &proposal)?;
+    let result =
+        ctx.accounts.committee.verify(epoch, slot, signer_bitmap, aggregated_and_compressed_g1_signature, &message);
+    record_verification(result, &message, Some(committee_key), Some(signer_bitmap))?;
+    let config = &mut ctx.accounts.config;
+    config.governance_nonce = config
+        .governance_nonce
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+
+    let accounts = ctx.accounts;
+    match proposal.action {
+        GovernanceAction::SetPaused { paused } => {
+            accounts.config.paused = paused;
+            msg!("Program paused: {}", paused);
+        }
+        GovernanceAction::UpdateConfig { registration_mode, max_committee_size } => {
+            require!(max_committee_size > 0, ErrorCode::InvalidCommitteeSize);
+            accounts.config.registration_mode = registration_mode;
+            accounts.config.max_committee_size = max_committee_size;
+        }
+        GovernanceAction::ProposeAdmin { new_admin } => {
+            accounts.config.pending_admin = Some(new_admin);
+            msg!("Admin transfer proposed to {}", new_admin);
+        }
+        GovernanceAction::SetBondParameters { min_bond, unbonding_delay_slots } => {
+            require!(accounts.config.bond_mint.is_some() && min_bond > 0, ErrorCode::InsufficientBond);
+            accounts.config.min_bond = min_bond;
+            accounts.config.unbonding_delay_slots = unbonding_delay_slots;
+        }
+        GovernanceAction::SetOptimisticParameters { optimistic_bond, dispute_window_slots } => {
+            require!(optimistic_bond > 0 && dispute_window_slots > 0, ErrorCode::InvalidOptimisticParameters);
+            accounts.config.optimistic_bond = optimistic_bond;
+            accounts.config.dispute_window_slots = dispute_window_slots;
+        }
+        GovernanceAction::SetWeightQuorum { committee, quorum_bps } => {
+            require!(quorum_bps <= 10_000, ErrorCode::InvalidThreshold);
+            let target = governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            target.current.quorum_bps = quorum_bps;
+        }
+        GovernanceAction::SetHandoffWindow { committee, handoff_window_slots } => {
+            let target = governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            target.handoff_window_slots = handoff_window_slots;
+        }
+        GovernanceAction::SetTimestampLimits { committee, max_staleness, max_future_skew } => {
+            require!(max_staleness >= 0 && max_future_skew >= 0, ErrorCode::InvalidTimestampLimits);
+            let target = governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            target.max_staleness = max_staleness;
+            target.max_future_skew = max_future_skew;
+        }
+        GovernanceAction::SetSnapshotRetention { committee, snapshot_retention_epochs } => {
+            let target = governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            target.snapshot_retention_epochs = snapshot_retention_epochs;
+        }
+        GovernanceAction::UnfreezeCommittee { committee } => {
+            let target = governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            require!(target.frozen, ErrorCode::NotFrozen);
+            target.frozen = false;
+            msg!("Committee {} unfrozen", committee);
+        }
+        GovernanceAction::AddFeed { feed_id, committee, settings } => {
+            settings.validate()?;
+            governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            let feed_registry = governance_registry(&mut accounts.feed_registry)?;
+            let policy =
+                FeedPolicy { feed_id, committee, settings, retired: false, frozen: false, frozen_slot: 0 };
+            feed_registry.register(policy)?;
+            emit!(FeedPolicyUpdated { policy });
+        }
+        GovernanceAction::UpdateFeedPolicy { feed_id, committee, settings } => {
+            settings.validate()?;
+            governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            let policy = governance_registry(&mut accounts.feed_registry)?.policy_mut(&feed_id)?;
+            require!(!policy.retired, ErrorCode::FeedRetired);
+            policy.committee = committee;
+            policy.settings = settings;
+            emit!(FeedPolicyUpdated { policy: *policy });
+        }
+        GovernanceAction::RetireFeed { feed_id } => {
+            let policy = governance_registry(&mut accounts.feed_registry)?.policy_mut(&feed_id)?;
+            policy.retired = true;
+            emit!(FeedPolicyUpdated { policy: *policy });
+        }
+        GovernanceAction::UnfreezeFeed { feed_id } => {
+            let policy = governance_registry(&mut accounts.feed_registry)?.policy_mut(&feed_id)?;
+            require!(policy.frozen, ErrorCode::NotFrozen);
+            policy.frozen = false;
+            emit!(FeedPolicyUpdated { policy: *policy });
+        }
+        GovernanceAction::SetGovernanceCommittee { committee } => {
+            governance_target(&mut accounts.committee, &mut accounts.target_committee, &committee)?;
+            accounts.config.governance_committee = Some(committee);
+            msg!("Governance committee set to {}", committee);
+        }
+    }
+    emit!(GovernanceExecuted {
+        committee: committee_key,
+        nonce: proposal.nonce,
+        action: proposal.action,
+    });
+    msg!("Governance proposal {} executed", proposal.nonce);
+    Ok(())
+}
@@ -0,0 +1,185 @@
+//! Merkle committees and proof buffers.
+
+use crate::*;
+
+/// Accounts structure for creating a Merkle committee.
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct InitializeMerkleCommittee<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MerkleCommittee::LEN,
+        seeds = [MERKLE_COMMITTEE_SEED, config.key().as_ref(), &committee_id.to_le_bytes()],
+        bump
+    )]
+    pub merkle_committee: Account<'info, MerkleCommittee>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the committee
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for replacing a Merkle committee's tree.
+#[derive(Accounts)]
+pub struct UpdateMerkleCommittee<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [MERKLE_COMMITTEE_SEED, config.key().as_ref(), &merkle_committee.committee_id.to_le_bytes()],
+        bump = merkle_committee.bump
+    )]
+    pub merkle_committee: Account<'info, MerkleCommittee>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for creating a proof buffer.
+#[derive(Accounts)]
+#[instruction(buffer_id: u64, capacity: u32)]
+pub struct InitProofBuffer<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProofBuffer::DATA_OFFSET + capacity as usize,
+        seeds = [PROOF_BUFFER_SEED, authority.key().as_ref(), &buffer_id.to_le_bytes()],
+        bump
+    )]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+    #[account(mut)]
+    pub authority: Signer<'info>, // Owner of the buffer, pays for it
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for writing into a proof buffer.
+#[derive(Accounts)]
+pub struct WriteProofBuffer<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority)]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+    pub authority: Signer<'info>,
+}
+
+/// Accounts structure for closing a proof buffer.
+#[derive(Accounts)]
+pub struct CloseProofBuffer<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::UnauthorizedAuthority, close = authority)]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+    #[account(mut)]
+    pub authority: Signer<'info>, // Owner of the buffer, receives the rent refund
+}
+
+/// Accounts structure for verifying a Merkle committee signature.
+#[derive(Accounts)]
+pub struct VerifyMerkleSignature<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [MERKLE_COMMITTEE_SEED, config.key().as_ref(), &merkle_committee.committee_id.to_le_bytes()],
+        bump = merkle_committee.bump
+    )]
+    pub merkle_committee: Account<'info, MerkleCommittee>,
+    pub proof_buffer: Option<Account<'info, ProofBuffer>>, // Buffer holding the signer proof, if not in instruction data
+}
+
+pub(crate) fn handle_initialize_merkle_committee(
+    ctx: Context<InitializeMerkleCommittee>,
+    committee_id: u64,
+    root: [u8; 32],
+    member_count: u32,
+    total_weight: u64,
+    quorum_weight: u64,
+) -> Result<()> {
+    require!(
+        quorum_weight > 0 && quorum_weight <= total_weight,
+        ErrorCode::InvalidThreshold
+    );
+    let committee = &mut ctx.accounts.merkle_committee;
+    committee.config = ctx.accounts.config.key();
+    committee.committee_id = committee_id;
+    committee.root = root;
+    committee.member_count = member_count;
+    committee.total_weight = total_weight;
+    committee.quorum_weight = quorum_weight;
+    committee.bump = ctx.bumps.merkle_committee;
+    emit!(MerkleCommitteeUpdated { merkle_committee: committee.key(), root, member_count, quorum_weight });
+    msg!("Merkle committee {} initialized", committee.key());
+    Ok(())
+}
+
+pub(crate) fn handle_update_merkle_committee(
+    ctx: Context<UpdateMerkleCommittee>,
+    root: [u8; 32],
+    member_count: u32,
+    total_weight: u64,
+    quorum_weight: u64,
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(
+        quorum_weight > 0 && quorum_weight <= total_weight,
+        ErrorCode::InvalidThreshold
+    );
+    let committee = &mut ctx.accounts.merkle_committee;
+    committee.root = root;
+    committee.member_count = member_count;
+    committee.total_weight = total_weight;
+    committee.quorum_weight = quorum_weight;
+    emit!(MerkleCommitteeUpdated { merkle_committee: committee.key(), root, member_count, quorum_weight });
+    Ok(())
+}
+
+pub(crate) fn handle_init_proof_buffer(ctx: Context<InitProofBuffer>, capacity: u32) -> Result<()> {
+    let buffer = &mut ctx.accounts.proof_buffer;
+    buffer.authority = ctx.accounts.authority.key();
+    buffer.capacity = capacity;
+    buffer.len = 0;
+    buffer.bump = ctx.bumps.proof_buffer;
+    Ok(())
+}
+
+pub(crate) fn handle_write_proof_buffer(ctx: Context<WriteProofBuffer>, offset: u32, bytes: Vec<u8>) -> Result<()> {
+    let buffer = &mut ctx.accounts.proof_buffer;
+    let end = offset
+        .checked_add(bytes.len() as u32)
+        .filter(|end| *end <= buffer.capacity)
+        .ok_or_else(|| error!(ErrorCode::ProofBufferOverflow))?;
+    buffer.len = buffer.len.max(end);
+
+    let start = ProofBuffer::DATA_OFFSET + offset as usize;
+    let buffer_info = buffer.to_account_info();
+    let mut data = buffer_info.try_borrow_mut_data()?;
+    data[start..start + bytes.len()].copy_from_slice(&bytes);
+    Ok(())
+}
+
+pub(crate) fn handle_verify_merkle_signature(
+    ctx: Context<VerifyMerkleSignature>,
+    signer_proof: Option<MerkleSignerProof>,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    message: Vec<u8>,
+) -> Result<()> {
+    let signer_proof = match (signer_proof, &ctx.accounts.proof_buffer) {
+        (Some(signer_proof), None) => signer_proof,
+        (None, Some(proof_buffer)) => ProofBuffer::load_signer_proof(proof_buffer)?,
+        _ => return err!(ErrorCode::InvalidMerkleProof),
+    };
+    let merkle_committee = &ctx.accounts.merkle_committee;
+    let result = merkle_committee.verify_signer_proof(&signer_proof).and_then(|signing_weight| {
+        let aggregated_g2_point = aggregate_keys(signer_proof.signers.iter().map(|signer| &signer.g2_point_key))?;
+        check_signature(
+            aggregated_g2_point,
+            G1CompressedPoint(aggregated_and_compressed_g1_signature),
+            &application_message(&message),
+        )?;
+        Ok(signing_weight)
+    });
+    let signing_weight = record_verification(result, &message, Some(merkle_committee.key()), None)?;
+
+    msg!("✅ Aggregated signature verified successfully with weight {}!", signing_weight);
+    Ok(())
+}
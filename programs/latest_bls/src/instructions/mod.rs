@@ -0,0 +1,31 @@
+//! Instruction handlers and their accounts, grouped by feature.
+
+pub mod config;
+pub mod governance;
+pub mod oracle;
+pub mod bonding;
+pub mod committee;
+pub mod merkle;
+pub mod registry;
+pub mod session;
+pub mod feeds;
+pub mod rewards;
+pub mod delegation;
+pub mod optimistic;
+pub mod vault;
+pub mod bridge;
+
+pub use config::*;
+pub use governance::*;
+pub use oracle::*;
+pub use bonding::*;
+pub use committee::*;
+pub use merkle::*;
+pub use registry::*;
+pub use session::*;
+pub use feeds::*;
+pub use rewards::*;
+pub use delegation::*;
+pub use optimistic::*;
+pub use vault::*;
+pub use bridge::*;
@@ -0,0 +1,156 @@
+//! Optimistic price reports and their disputes.
+
+use crate::*;
+
+/// Accounts structure for posting an optimistic report.
+#[derive(Accounts)]
+#[instruction(report: PriceReport)]
+pub struct PostOptimisticReport<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(seeds = [FEED_SEED, config.key().as_ref(), &feed.feed_id], bump = feed.bump)]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(
+        init,
+        payer = poster,
+        space = 8 + OptimisticReport::LEN,
+        seeds = [OPTIMISTIC_SEED, feed.key().as_ref(), &report.round_id.to_le_bytes(), poster.key().as_ref()],
+        bump
+    )]
+    pub optimistic_report: Account<'info, OptimisticReport>, // Pending claim, holds the bond
+    #[account(mut)]
+    pub poster: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for disputing an optimistic report.
+#[derive(Accounts)]
+pub struct ChallengeOptimisticReport<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(seeds = [FEED_SEED, config.key().as_ref(), &feed.feed_id], bump = feed.bump)]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(mut, has_one = feed @ ErrorCode::FeedMismatch, close = challenger)]
+    pub optimistic_report: Account<'info, OptimisticReport>,
+    #[account(mut)]
+    pub challenger: Signer<'info>, // Receives the bond and rent
+}
+
+/// Accounts structure for finalizing an optimistic report.
+#[derive(Accounts)]
+pub struct FinalizeOptimisticReport<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(seeds = [FEED_REGISTRY_SEED, config.key().as_ref()], bump = feed_registry.bump)]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(mut, seeds = [FEED_SEED, config.key().as_ref(), &feed.feed_id], bump = feed.bump)]
+    pub feed: Account<'info, PriceFeed>,
+    #[account(
+        mut,
+        has_one = feed @ ErrorCode::FeedMismatch,
+        has_one = poster @ ErrorCode::UnauthorizedAuthority,
+        close = poster
+    )]
+    pub optimistic_report: Account<'info, OptimisticReport>,
+    /// CHECK: Must match the claim's poster; only receives lamports.
+    #[account(mut)]
+    pub poster: UncheckedAccount<'info>, // Relayer that posted the report, receives the refund
+}
+
+pub(crate) fn handle_post_optimistic_report(ctx: Context<PostOptimisticReport>, report: PriceReport) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.config;
+    require!(config.dispute_window_slots > 0, ErrorCode::OptimisticDisabled);
+    let feed = &ctx.accounts.feed;
+    let policy = ctx.accounts.feed_registry.policy(&feed.feed_id, &ctx.accounts.committee.key())?;
+    policy.check_report(&ctx.accounts.committee, &report, clock.unix_timestamp)?;
+    require!(
+        report.is_newer_than(&feed.latest) && report.is_newer_than(&feed.optimistic),
+        ErrorCode::StaleReport
+    );
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.poster.to_account_info(),
+                to: ctx.accounts.optimistic_report.to_account_info(),
+            },
+        ),
+        config.optimistic_bond,
+    )?;
+
+    let optimistic_report = &mut ctx.accounts.optimistic_report;
+    optimistic_report.feed = feed.key();
+    optimistic_report.poster = ctx.accounts.poster.key();
+    optimistic_report.report = report;
+    optimistic_report.bond = config.optimistic_bond;
+    optimistic_report.final_slot = clock.slot.saturating_add(config.dispute_window_slots);
+    optimistic_report.bump = ctx.bumps.optimistic_report;
+    emit!(OptimisticReportPosted {
+        feed: feed.key(),
+        poster: optimistic_report.poster,
+        report,
+        final_slot: optimistic_report.final_slot,
+    });
+    Ok(())
+}
+
+pub(crate) fn handle_challenge_optimistic_report(
+    ctx: Context<ChallengeOptimisticReport>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    report: Vec<u8>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let claim = &ctx.accounts.optimistic_report;
+    require!(clock.slot < claim.final_slot, ErrorCode::DisputeWindowClosed);
+    let committee = &ctx.accounts.committee;
+    ctx.accounts.feed_registry.policy(&ctx.accounts.feed.feed_id, &committee.key())?;
+    let message = price_report_message(&report);
+    let result = committee
+        .verify(epoch, clock.slot, signer_bitmap, aggregated_and_compressed_g1_signature, &message);
+    record_verification(result, &report, Some(committee.key()), Some(signer_bitmap))?;
+    let report = PriceReport::try_from_slice(&report).map_err(|_| error!(ErrorCode::InvalidReport))?;
+    require!(report.contradicts(&claim.report), ErrorCode::NotEquivocation);
+
+    emit!(OptimisticReportDisputed {
+        feed: claim.feed,
+        poster: claim.poster,
+        challenger: ctx.accounts.challenger.key(),
+        round_id: report.round_id,
+        bond: claim.bond,
+    });
+    msg!("Optimistic report for round {} disputed", report.round_id);
+    Ok(())
+}
+
+pub(crate) fn handle_finalize_optimistic_report(ctx: Context<FinalizeOptimisticReport>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let report = ctx.accounts.optimistic_report.report;
+    require!(slot >= ctx.accounts.optimistic_report.final_slot, ErrorCode::DisputeWindowOpen);
+    let policy = ctx.accounts.feed_registry.find_policy(&ctx.accounts.feed.feed_id)?;
+    require!(!policy.frozen, ErrorCode::FeedFrozen);
+    let retired = policy.retired;
+    let feed = &mut ctx.accounts.feed;
+    if !retired && report.is_newer_than(&feed.optimistic) {
+        feed.optimistic = report;
+        feed.optimistic_update_slot = slot;
+        msg!("Optimistic price finalized: {} at round {}", report.price, report.round_id);
+    }
+    Ok(())
+}
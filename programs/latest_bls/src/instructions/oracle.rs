@@ -0,0 +1,178 @@
+//! Oracle registration and verification against a list of oracles.
+
+use crate::*;
+
+/// Accounts structure for initializing an Oracle.
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleAccount::LEN,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>, // Required in allowlist mode
+    pub admin: Option<Signer<'info>>, // Required in admin-only mode
+    #[account(mut, seeds = [BOND_VAULT_SEED, config.key().as_ref()], bump = config.bond_vault_bump)]
+    pub bond_vault: Option<Account<'info, TokenAccount>>, // Required once bonding is enabled
+    #[account(mut)]
+    pub authority_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>, // Required once bonding is enabled
+}
+
+/// Accounts structure for deregistering an Oracle.
+#[derive(Accounts)]
+pub struct DeregisterOracle<'info> {
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAuthority,
+        close = authority
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>, // The authority that registered the oracle, receives the rent refund
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub committee: Option<Account<'info, Committee>>, // Required when the oracle belongs to a committee
+}
+
+/// Accounts structure for verifying a signature.
+#[derive(Accounts)]
+pub struct VerifySignature<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub(crate) fn handle_initialize_oracle(
+    ctx: Context<InitializeOracle>,
+    g2_point_key: [u8; 128],
+    pop_signature: [u8; 32],
+) -> Result<()> {
+    check_proof_of_possession(&g2_point_key, pop_signature)?;
+    let config = &ctx.accounts.config;
+    match config.registration_mode {
+        RegistrationMode::Open => {}
+        RegistrationMode::Allowlist => {
+            let entry = ctx
+                .accounts
+                .allowlist_entry
+                .as_ref()
+                .ok_or_else(|| error!(ErrorCode::NotAllowlisted))?;
+            require_keys_eq!(entry.authority, ctx.accounts.authority.key(), ErrorCode::NotAllowlisted);
+        }
+        RegistrationMode::AdminOnly => {
+            let admin = ctx.accounts.admin.as_ref().ok_or_else(|| error!(ErrorCode::UnauthorizedAdmin))?;
+            require_keys_eq!(admin.key(), config.admin, ErrorCode::UnauthorizedAdmin);
+        }
+    }
+
+    let accounts = &ctx.accounts;
+    let bonded = if config.bond_mint.is_some() {
+        let (Some(bond_vault), Some(authority_token_account), Some(token_program)) = (
+            accounts.bond_vault.as_ref(),
+            accounts.authority_token_account.as_ref(),
+            accounts.token_program.as_ref(),
+        ) else {
+            return err!(ErrorCode::InsufficientBond);
+        };
+        deposit_bond(authority_token_account, bond_vault, &accounts.authority, token_program, config.min_bond)?;
+        config.min_bond
+    } else {
+        0
+    };
+
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.authority = ctx.accounts.authority.key(); // Record who owns the oracle
+    oracle.g2_point_key = g2_point_key; // Store the compressed G2 public key
+    oracle.bump = ctx.bumps.oracle;
+    oracle.bonded = bonded;
+    oracle.unbonding = Vec::new();
+    oracle.jailed = false;
+    oracle.delegated = 0;
+    oracle.commission_bps = 0;
+    oracle.operator_rewards = 0;
+    oracle.reward_per_share = 0;
+    oracle.weight_checkpoints = Vec::new();
+    oracle.pending_delegated = 0;
+    oracle.pending_delegations = 0;
+    oracle.accepts_delegations = false;
+    emit!(OracleRegistered {
+        oracle: oracle.key(),
+        authority: oracle.authority,
+        g2_point_key,
+    });
+    msg!("Oracle {} initialized", oracle.key());
+    Ok(())
+}
+
+pub(crate) fn handle_deregister_oracle(ctx: Context<DeregisterOracle>) -> Result<()> {
+    let oracle_key = ctx.accounts.oracle.key();
+    require!(!ctx.accounts.oracle.jailed, ErrorCode::OracleJailed);
+    require!(
+        ctx.accounts.oracle.bonded == 0
+            && ctx.accounts.oracle.unbonding.is_empty()
+            && ctx.accounts.oracle.delegated == 0,
+        ErrorCode::BondOutstanding
+    );
+    remove_from_committee(&oracle_key, ctx.accounts.oracle.committee, ctx.accounts.committee.as_mut())?;
+    emit!(OracleDeregistered {
+        oracle: oracle_key,
+        authority: ctx.accounts.oracle.authority,
+        committee: ctx.accounts.oracle.committee,
+    });
+    msg!("Oracle {} deregistered", oracle_key);
+    Ok(())
+}
+
+pub(crate) fn handle_verify_signature(
+    ctx: Context<VerifySignature>,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    message: Vec<u8>,
+) -> Result<()> {
+    let aggregated_signature = G1CompressedPoint(aggregated_and_compressed_g1_signature); // Load the compressed G1 signature
+    
+    // Ensure the committee fits within the configured size
+    require!(
+        ctx.remaining_accounts.len() <= ctx.accounts.config.max_committee_size as usize,
+        ErrorCode::CommitteeTooLarge
+    );
+
+    // Ensure there are remaining accounts to process
+    let mut iter = ctx.remaining_accounts.iter();
+    let first_account_info = iter.next().ok_or_else(|| error!(ErrorCode::NoOraclesProvided))?;
+
+    let config_key = ctx.accounts.config.key();
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+
+    // Step 1: Initialize `aggregated_g2_point` from the first account
+    let first_oracle = load_oracle(first_account_info, &config_key)?;
+    seen.push(first_account_info.key());
+    let mut aggregated_g2_point = G2Point(first_oracle.g2_point_key); // Start aggregation with the first G2 point
+
+    // Step 2: Iterate through the remaining accounts and aggregate G2 points
+    for account_info in iter {
+        // Deserialize the OracleAccount from account data, rejecting repeats
+        require!(!seen.contains(account_info.key), ErrorCode::DuplicateOracle);
+        let oracle = load_oracle(account_info, &config_key)?;
+        seen.push(account_info.key());
+
+        // Aggregate G2 points
+        aggregated_g2_point = aggregated_g2_point + G2Point(oracle.g2_point_key);
+    }
+           // Step 4: Verify the aggregated G1 signature against the aggregated G2 point and message.
+    let result = check_signature(aggregated_g2_point, aggregated_signature, &application_message(&message));
+    record_verification(result, &message, None, None)?;
+
+    msg!("✅ Aggregated signature verified successfully!");
+    Ok(())
+}
@@ -0,0 +1,289 @@
+//! Paged member registries.
+
+use crate::*;
+
+/// Sum the keys and weights of the registry members at `signer_indices`, reading them from the
+/// registry pages in `pages`. Indices must be strictly increasing and pages in ascending order.
+pub(crate) fn aggregate_registry_members<'info>(
+    registry: &Pubkey,
+    pages: &'info [AccountInfo<'info>],
+    signer_indices: &[u32],
+) -> Result<(G2Point, u64)> {
+    let mut pages = pages.iter();
+    let mut current: Option<AccountLoader<'info, RegistryPage>> = None;
+    let mut aggregated: Option<G2Point> = None;
+    let mut signing_weight: u64 = 0;
+    let mut previous_index: Option<u32> = None;
+
+    for &index in signer_indices {
+        if let Some(previous) = previous_index {
+            require!(index > previous, ErrorCode::InvalidMemberIndex);
+        }
+        previous_index = Some(index);
+        let page_index = index / PAGE_CAPACITY as u32;
+        let slot = (index % PAGE_CAPACITY as u32) as usize;
+
+        // Advance to the page holding this index
+        loop {
+            if let Some(loader) = &current {
+                if loader.load()?.page_index == page_index {
+                    break;
+                }
+            }
+            let page_info = pages.next().ok_or_else(|| error!(ErrorCode::InvalidMemberIndex))?;
+            let loader = AccountLoader::<RegistryPage>::try_from(page_info)?;
+            require_keys_eq!(loader.load()?.registry, *registry, ErrorCode::InvalidRegistryPage);
+            current = Some(loader);
+        }
+
+        let loader = current.as_ref().ok_or_else(|| error!(ErrorCode::InvalidMemberIndex))?;
+        let page = loader.load()?;
+        let entry = page.entries.get(slot).filter(|entry| entry.status == RegistryEntry::ACTIVE);
+        let entry = entry.ok_or_else(|| error!(ErrorCode::InvalidMemberIndex))?;
+        signing_weight = signing_weight
+            .checked_add(entry.weight)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        aggregated = Some(match aggregated {
+            Some(aggregated) => aggregated + G2Point(entry.g2_point_key),
+            None => G2Point(entry.g2_point_key),
+        });
+    }
+
+    let aggregated = aggregated.ok_or_else(|| error!(ErrorCode::NoOraclesProvided))?;
+    Ok((aggregated, signing_weight))
+}
+
+/// Accounts structure for creating a paged registry.
+#[derive(Accounts)]
+#[instruction(registry_id: u64)]
+pub struct InitializePagedRegistry<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PagedRegistry::LEN,
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry_id.to_le_bytes()],
+        bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the registry
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for admin-only paged registry changes.
+#[derive(Accounts)]
+pub struct UpdatePagedRegistry<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for adding a page to a paged registry.
+#[derive(Accounts)]
+pub struct AddRegistryPage<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<RegistryPage>(),
+        seeds = [REGISTRY_PAGE_SEED, registry.key().as_ref(), &registry.page_count.to_le_bytes()],
+        bump
+    )]
+    pub page: AccountLoader<'info, RegistryPage>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the page
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for changing the members of a registry page.
+#[derive(Accounts)]
+pub struct UpdateRegistryPage<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    #[account(mut, constraint = page.load()?.registry == registry.key() @ ErrorCode::InvalidRegistryPage)]
+    pub page: AccountLoader<'info, RegistryPage>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for verifying a signature by paged registry members.
+#[derive(Accounts)]
+pub struct VerifyPagedSignature<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+}
+
+pub(crate) fn handle_initialize_paged_registry(
+    ctx: Context<InitializePagedRegistry>,
+    registry_id: u64,
+    quorum_weight: u64,
+) -> Result<()> {
+    require!(quorum_weight > 0, ErrorCode::InvalidThreshold);
+    let registry = &mut ctx.accounts.registry;
+    registry.config = ctx.accounts.config.key();
+    registry.registry_id = registry_id;
+    registry.page_count = 0;
+    registry.member_count = 0;
+    registry.total_weight = 0;
+    registry.quorum_weight = quorum_weight;
+    registry.version = 0;
+    registry.bump = ctx.bumps.registry;
+    Ok(())
+}
+
+pub(crate) fn handle_set_registry_quorum(ctx: Context<UpdatePagedRegistry>, quorum_weight: u64) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(quorum_weight > 0, ErrorCode::InvalidThreshold);
+    ctx.accounts.registry.quorum_weight = quorum_weight;
+    Ok(())
+}
+
+pub(crate) fn handle_add_registry_page(ctx: Context<AddRegistryPage>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    let mut page = ctx.accounts.page.load_init()?;
+    page.registry = registry.key();
+    page.page_index = registry.page_count;
+    registry.page_count = registry
+        .page_count
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    Ok(())
+}
+
+pub(crate) fn handle_append_member(
+    ctx: Context<UpdateRegistryPage>,
+    g2_point_key: [u8; 128],
+    weight: u64,
+    pop_signature: [u8; 32],
+) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    require!(weight > 0, ErrorCode::InvalidWeight);
+    check_proof_of_possession(&g2_point_key, pop_signature)?;
+    let mut page = ctx.accounts.page.load_mut()?;
+    let slot = page.len as usize;
+    require!(slot < PAGE_CAPACITY, ErrorCode::PageFull);
+    page.entries[slot] = RegistryEntry {
+        g2_point_key,
+        weight,
+        status: RegistryEntry::ACTIVE,
+        _padding: [0; 7],
+    };
+    page.len += 1;
+
+    let registry = &mut ctx.accounts.registry;
+    registry.member_count = registry.member_count.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    registry.total_weight = registry
+        .total_weight
+        .checked_add(weight)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    registry.version = registry.version.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    let index = page.page_index * PAGE_CAPACITY as u32 + slot as u32;
+    emit!(RegistryMemberUpdated { registry: registry.key(), index, g2_point_key, weight, active: true });
+    msg!("Member appended at index {}", index);
+    Ok(())
+}
+
+pub(crate) fn handle_remove_member(ctx: Context<UpdateRegistryPage>, slot: u32) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let mut page = ctx.accounts.page.load_mut()?;
+    require!(slot < page.len, ErrorCode::InvalidMemberIndex);
+    let entry = &mut page.entries[slot as usize];
+    require!(entry.status == RegistryEntry::ACTIVE, ErrorCode::InvalidMemberIndex);
+    entry.status = RegistryEntry::REMOVED;
+    let (g2_point_key, weight) = (entry.g2_point_key, entry.weight);
+
+    let registry = &mut ctx.accounts.registry;
+    registry.member_count = registry.member_count.checked_sub(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    registry.total_weight = registry
+        .total_weight
+        .checked_sub(weight)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    registry.version = registry.version.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    let index = page.page_index * PAGE_CAPACITY as u32 + slot;
+    emit!(RegistryMemberUpdated { registry: registry.key(), index, g2_point_key, weight, active: false });
+    Ok(())
+}
+
+pub(crate) fn handle_compact_page(ctx: Context<UpdateRegistryPage>) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    let mut page = ctx.accounts.page.load_mut()?;
+    let len = page.len as usize;
+    let mut write = 0usize;
+    for read in 0..len {
+        if page.entries[read].status == RegistryEntry::ACTIVE {
+            page.entries[write] = page.entries[read];
+            write += 1;
+        }
+    }
+    for entry in page.entries[write..len].iter_mut() {
+        *entry = RegistryEntry::zeroed();
+    }
+    page.len = write as u32;
+    let registry = &mut ctx.accounts.registry;
+    registry.version = registry.version.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    Ok(())
+}
+
+pub(crate) fn handle_verify_paged_signature<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyPagedSignature<'info>>,
+    signer_indices: Vec<u32>,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    message: Vec<u8>,
+) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+    let result = aggregate_registry_members(&registry.key(), ctx.remaining_accounts, &signer_indices).and_then(
+        |(aggregated_g2_point, signing_weight)| {
+            require!(signing_weight >= registry.quorum_weight, ErrorCode::ThresholdNotMet);
+            check_signature(
+                aggregated_g2_point,
+                G1CompressedPoint(aggregated_and_compressed_g1_signature),
+                &application_message(&message),
+            )?;
+            Ok(signing_weight)
+        },
+    );
+    let signing_weight = record_verification(result, &message, Some(registry.key()), None)?;
+
+    msg!("✅ Aggregated signature verified successfully with weight {}!", signing_weight);
+    Ok(())
+}
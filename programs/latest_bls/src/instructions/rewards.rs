@@ -0,0 +1,156 @@
+//! The reward pool paying signers, and operator commissions.
+
+use crate::*;
+
+/// Pay the members of `committee`'s `epoch` set in `signer_bitmap` their share of the update fee,
+/// if the program has a reward pool that charges one.
+pub(crate) fn credit_signers(
+    reward_pool: Option<&mut Account<RewardPool>>,
+    committee: &Committee,
+    epoch: u64,
+    signer_bitmap: u32,
+    slot: u64,
+) -> Result<()> {
+    match reward_pool {
+        Some(reward_pool) if reward_pool.fee_per_update > 0 => {
+            reward_pool.credit(committee.set_for_epoch(epoch, slot)?, signer_bitmap)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Accounts structure for creating the reward pool.
+#[derive(Accounts)]
+pub struct InitializeRewardPool<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RewardPool::LEN,
+        seeds = [REWARD_POOL_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // Current admin, pays for the pool
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for changing the per-update fee.
+#[derive(Accounts)]
+pub struct SetUpdateFee<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts structure for funding the reward pool.
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(mut)]
+    pub funder: Signer<'info>, // Consumer paying for updates
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for claiming an oracle's rewards.
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>, // Authority of the oracle, receives the rewards
+}
+
+/// Accounts structure for setting an oracle's commission.
+#[derive(Accounts)]
+pub struct SetCommission<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut, seeds = [REWARD_POOL_SEED, config.key().as_ref()], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, config.key().as_ref(), authority.key().as_ref()],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAuthority
+    )]
+    pub oracle: Account<'info, OracleAccount>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handle_initialize_reward_pool(ctx: Context<InitializeRewardPool>, fee_per_update: u64) -> Result<()> {
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.config = ctx.accounts.config.key();
+    reward_pool.fee_per_update = fee_per_update;
+    reward_pool.available = 0;
+    reward_pool.balances = Vec::new();
+    reward_pool.bump = ctx.bumps.reward_pool;
+    Ok(())
+}
+
+pub(crate) fn handle_set_update_fee(ctx: Context<SetUpdateFee>, fee_per_update: u64) -> Result<()> {
+    ctx.accounts.config.check_admin_governs()?;
+    ctx.accounts.reward_pool.fee_per_update = fee_per_update;
+    Ok(())
+}
+
+pub(crate) fn handle_fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.reward_pool.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.available = reward_pool
+        .available
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    Ok(())
+}
+
+pub(crate) fn handle_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let oracle = &mut ctx.accounts.oracle;
+    let pooled = ctx.accounts.reward_pool.take_balance(&oracle.key());
+    oracle.accrue_rewards(pooled)?;
+    let amount = std::mem::take(&mut oracle.operator_rewards);
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    ctx.accounts.reward_pool.sub_lamports(amount)?;
+    ctx.accounts.authority.add_lamports(amount)?;
+    msg!("Oracle {} claimed {} lamports", ctx.accounts.oracle.key(), amount);
+    Ok(())
+}
+
+pub(crate) fn handle_set_commission(ctx: Context<SetCommission>, commission_bps: u16) -> Result<()> {
+    require!(commission_bps <= 10_000, ErrorCode::InvalidCommission);
+    let oracle = &mut ctx.accounts.oracle;
+    let pooled = ctx.accounts.reward_pool.take_balance(&oracle.key());
+    oracle.accrue_rewards(pooled)?;
+    oracle.commission_bps = commission_bps;
+    Ok(())
+}
+
+pub(crate) fn handle_set_accepts_delegations(ctx: Context<SetCommission>, accepts_delegations: bool) -> Result<()> {
+    ctx.accounts.oracle.accepts_delegations = accepts_delegations;
+    Ok(())
+}
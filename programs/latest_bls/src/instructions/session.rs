@@ -0,0 +1,148 @@
+//! Verification sessions accumulating signers across transactions.
+
+use crate::*;
+
+/// Accounts structure for starting a verification session.
+#[derive(Accounts)]
+#[instruction(session_id: u64)]
+pub struct BeginVerificationSession<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VerificationSession::LEN,
+        seeds = [SESSION_SEED, registry.key().as_ref(), owner.key().as_ref(), &session_id.to_le_bytes()],
+        bump
+    )]
+    pub session: Account<'info, VerificationSession>,
+    #[account(mut)]
+    pub owner: Signer<'info>, // Only signer allowed to continue the session, pays for it
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for adding signers to a verification session.
+#[derive(Accounts)]
+pub struct AccumulateSessionSigners<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::UnauthorizedAuthority,
+        has_one = registry @ ErrorCode::InvalidRegistryPage,
+        constraint = !session.verified @ ErrorCode::SessionAlreadyVerified,
+        constraint = session.registry_version == registry.version @ ErrorCode::RegistryChanged
+    )]
+    pub session: Account<'info, VerificationSession>,
+    pub owner: Signer<'info>,
+}
+
+/// Accounts structure for finishing a verification session.
+#[derive(Accounts)]
+pub struct FinalizeVerificationSession<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [REGISTRY_SEED, config.key().as_ref(), &registry.registry_id.to_le_bytes()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, PagedRegistry>,
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::UnauthorizedAuthority,
+        has_one = registry @ ErrorCode::InvalidRegistryPage,
+        constraint = !session.verified @ ErrorCode::SessionAlreadyVerified,
+        constraint = session.registry_version == registry.version @ ErrorCode::RegistryChanged
+    )]
+    pub session: Account<'info, VerificationSession>,
+    pub owner: Signer<'info>,
+}
+
+/// Accounts structure for closing a verification session.
+#[derive(Accounts)]
+pub struct CloseVerificationSession<'info> {
+    #[account(mut, has_one = owner @ ErrorCode::UnauthorizedAuthority, close = owner)]
+    pub session: Account<'info, VerificationSession>,
+    #[account(mut)]
+    pub owner: Signer<'info>, // Owner of the session, receives the rent refund
+}
+
+pub(crate) fn handle_begin_verification_session(
+    ctx: Context<BeginVerificationSession>,
+    message_hash: [u8; 32],
+    aggregated_and_compressed_g1_signature: [u8; 32],
+) -> Result<()> {
+    let session = &mut ctx.accounts.session;
+    session.owner = ctx.accounts.owner.key();
+    session.registry = ctx.accounts.registry.key();
+    session.registry_version = ctx.accounts.registry.version;
+    session.message_hash = message_hash;
+    session.signature = aggregated_and_compressed_g1_signature;
+    session.next_index = 0;
+    session.signer_count = 0;
+    session.signing_weight = 0;
+    session.partial_aggregate = [0; 128];
+    session.verified = false;
+    session.bump = ctx.bumps.session;
+    Ok(())
+}
+
+pub(crate) fn handle_accumulate_session_signers<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AccumulateSessionSigners<'info>>,
+    signer_indices: Vec<u32>,
+) -> Result<()> {
+    let session = &mut ctx.accounts.session;
+    let first = *signer_indices.first().ok_or_else(|| error!(ErrorCode::NoOraclesProvided))?;
+    require!(first >= session.next_index, ErrorCode::InvalidMemberIndex);
+
+    let (aggregated_g2_point, weight) =
+        aggregate_registry_members(&session.registry, ctx.remaining_accounts, &signer_indices)?;
+    session.partial_aggregate = if session.signer_count == 0 {
+        aggregated_g2_point.0
+    } else {
+        (G2Point(session.partial_aggregate) + aggregated_g2_point).0
+    };
+    session.signer_count += signer_indices.len() as u32;
+    session.signing_weight = session
+        .signing_weight
+        .checked_add(weight)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    session.next_index = signer_indices[signer_indices.len() - 1] // Checkpoint the cursor
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+    msg!("Session cursor at {} with {} signers", session.next_index, session.signer_count);
+    Ok(())
+}
+
+pub(crate) fn handle_finalize_verification_session(
+    ctx: Context<FinalizeVerificationSession>,
+    message: Vec<u8>,
+) -> Result<()> {
+    let session = &mut ctx.accounts.session;
+    require!(hash(&message).to_bytes() == session.message_hash, ErrorCode::MessageMismatch);
+    let quorum_weight = ctx.accounts.registry.quorum_weight;
+    let result = (|| {
+        require!(session.signer_count > 0, ErrorCode::NoOraclesProvided);
+        require!(session.signing_weight >= quorum_weight, ErrorCode::ThresholdNotMet);
+        check_signature(
+            G2Point(session.partial_aggregate),
+            G1CompressedPoint(session.signature),
+            &application_message(&message),
+        )
+    })();
+    record_verification(result, &message, Some(session.registry), None)?;
+    session.verified = true;
+
+    msg!("✅ Aggregated signature verified successfully with weight {}!", session.signing_weight);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! Committee-controlled vaults.
+
+use crate::*;
+
+/// Accounts structure for creating a committee vault.
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CommitteeVault::LEN,
+        seeds = [VAULT_SEED, committee.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, CommitteeVault>,
+    #[account(mut)]
+    pub payer: Signer<'info>, // Pays for the vault
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts structure for executing a committee-signed vault transfer.
+#[derive(Accounts)]
+pub struct ExecuteVaultTransfer<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, constraint = !config.paused @ ErrorCode::ProgramPaused)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [COMMITTEE_SEED, config.key().as_ref(), &committee.committee_id.to_le_bytes()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, Committee>,
+    #[account(mut, seeds = [VAULT_SEED, committee.key().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, CommitteeVault>, // Vault PDA, holds SOL and owns token accounts
+    /// CHECK: Must equal the intent's destination; only receives lamports or tokens.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>, // Receiving account, or token account for SPL transfers
+    #[account(mut, token::authority = vault)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>, // SPL token program, for SPL transfers
+}
+
+pub(crate) fn handle_initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.committee = ctx.accounts.committee.key();
+    vault.nonce = 0;
+    vault.bump = ctx.bumps.vault;
+    msg!("Vault {} created for committee {}", vault.key(), vault.committee);
+    Ok(())
+}
+
+pub(crate) fn handle_execute_vault_transfer(
+    ctx: Context<ExecuteVaultTransfer>,
+    epoch: u64,
+    signer_bitmap: u32,
+    aggregated_and_compressed_g1_signature: [u8; 32],
+    intent: TransferIntent,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let vault_key = ctx.accounts.vault.key();
+    require!(intent.amount > 0, ErrorCode::InvalidAmount);
+    require!(intent.nonce == ctx.accounts.vault.nonce, ErrorCode::InvalidNonce);
+    require!(slot <= intent.expiry_slot, ErrorCode::ProposalExpired);
+    require_keys_eq!(ctx.accounts.destination.key(), intent.destination, ErrorCode::InvalidDestination);
+    let message = vault_transfer_message(&vault_key, &intent)?;
+    let committee = &ctx.accounts.committee;
+    let result = committee.verify(epoch, slot, signer_bitmap, aggregated_and_compressed_g1_signature, &message);
+    record_verification(result, &message, Some(committee.key()), Some(signer_bitmap))?;
+    let vault = &mut ctx.accounts.vault;
+    vault.nonce = vault.nonce.checked_add(1).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+
+    let accounts = &ctx.accounts;
+    match intent.mint {
+        None => {
+            let vault_info = accounts.vault.to_account_info();
+            let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+            require!(
+                vault_info.lamports().saturating_sub(rent_exempt) >= intent.amount,
+                ErrorCode::InsufficientVaultBalance
+            );
+            accounts.vault.sub_lamports(intent.amount)?;
+            accounts.destination.add_lamports(intent.amount)?;
+        }
+        Some(mint) => {
+            let (Some(vault_token_account), Some(token_program)) =
+                (accounts.vault_token_account.as_ref(), accounts.token_program.as_ref())
+            else {
+                return err!(ErrorCode::InvalidVaultTokenAccount);
+            };
+            require_keys_eq!(vault_token_account.mint, mint, ErrorCode::InvalidVaultTokenAccount);
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[VAULT_SEED, accounts.vault.committee.as_ref(), &[accounts.vault.bump]]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: vault_token_account.to_account_info(),
+                        to: accounts.destination.to_account_info(),
+                        authority: accounts.vault.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                intent.amount,
+            )?;
+        }
+    }
+    emit!(VaultTransferExecuted {
+        vault: vault_key,
+        destination: intent.destination,
+        mint: intent.mint,
+        amount: intent.amount,
+        nonce: intent.nonce,
+    });
+    msg!("Vault transfer {} executed", intent.nonce);
+    Ok(())
+}
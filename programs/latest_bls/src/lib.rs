@@ -1,149 +1,5047 @@
 use anchor_lang::prelude::*;
-use solana_alt_bn128_bls::{G1CompressedPoint, G1Point, G2CompressedPoint, Sha256Normalized};
-use std::convert::TryFrom;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::alt_bn128::prelude::{alt_bn128_pairing, ALT_BN128_PAIRING_ELEMENT_LEN};
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use solana_alt_bn128_bls::*;
+
 pub mod utils;
+pub mod crypto;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod messages;
+pub mod state;
 
+pub use crypto::*;
+pub use error::ErrorCode;
+pub use events::*;
+pub use instructions::*;
+pub use messages::*;
+pub use state::*;
 
 declare_id!("E4LXwvzGcZzdJfURbdLDW2BLqz73JLq9ncBd6NteYik");
-#[program]
-pub mod latest_bls {
-    use solana_program::log::sol_log_compute_units;
 
+/// Seed for the program configuration PDA.
+pub const CONFIG_SEED: &[u8] = b"config";
+/// Seed for allowlist entry PDAs.
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+/// Seed for oracle PDAs, followed by the config (registry) address and the oracle authority.
+pub const ORACLE_SEED: &[u8] = b"oracle";
+pub const BOND_VAULT_SEED: &[u8] = b"bond_vault";
+pub const REWARD_POOL_SEED: &[u8] = b"reward_pool";
+pub const MAX_REWARD_BALANCES: usize = 128;
+pub const DELEGATION_SEED: &[u8] = b"delegation";
+pub const MAX_WEIGHT_CHECKPOINTS: usize = 8;
+pub const REWARD_SCALE: u128 = 1_000_000_000_000;
+pub const MAX_UNBONDING_REQUESTS: usize = 8;
+/// Seed for committee PDAs, followed by the config address and the committee id.
+pub const COMMITTEE_SEED: &[u8] = b"committee";
+/// Seed for committee snapshot PDAs, followed by the committee address and the epoch.
+pub const SNAPSHOT_SEED: &[u8] = b"snapshot";
+/// Seed for Merkle committee PDAs, followed by the config address and the committee id.
+pub const MERKLE_COMMITTEE_SEED: &[u8] = b"merkle_committee";
+/// Seed for proof buffer PDAs, followed by the authority and the buffer id.
+pub const PROOF_BUFFER_SEED: &[u8] = b"proof_buffer";
+/// Seed for paged registry PDAs, followed by the config address and the registry id.
+pub const REGISTRY_SEED: &[u8] = b"registry";
+/// Seed for registry page PDAs, followed by the registry address and the page index.
+pub const REGISTRY_PAGE_SEED: &[u8] = b"registry_page";
+/// Seed for price feed PDAs, followed by the config address and the feed id.
+pub const FEED_SEED: &[u8] = b"feed";
+pub const FEED_REGISTRY_SEED: &[u8] = b"feed_registry";
+pub const MAX_FEEDS: usize = 64;
+pub const HISTORY_SEED: &[u8] = b"history";
+pub const HISTORY_CAPACITY: usize = 128;
+pub const ATTESTED_ROOT_SEED: &[u8] = b"attested_root";
+pub const OPTIMISTIC_SEED: &[u8] = b"optimistic";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const BRIDGE_SEED: &[u8] = b"bridge";
+pub const WRAPPED_MINT_SEED: &[u8] = b"wrapped_mint";
+pub const DEPOSIT_SEED: &[u8] = b"deposit";
+/// Seed for verification session PDAs, followed by the registry, the owner and the session id.
+pub const SESSION_SEED: &[u8] = b"session";
+/// Number of member entries in one registry page.
+pub const PAGE_CAPACITY: usize = 64;
+/// Maximum number of members in a stored committee, bounded by the account size and the `u32` signer bitmap.
+pub const MAX_COMMITTEE_MEMBERS: usize = 24;
+
+#[program]
+pub mod latest_bls{
     use super::*;
 
+    /// Create the program configuration. The signer must be the program's upgrade authority
+    /// and becomes the admin.
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        registration_mode: RegistrationMode, // Who may register oracles
+        max_committee_size: u16,             // Maximum number of oracles aggregated in one verification
+    ) -> Result<()> {
+        instructions::handle_initialize_config(ctx, registration_mode, max_committee_size)
+    }
+
+    /// Update the registration mode and the maximum committee size.
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        registration_mode: RegistrationMode,
+        max_committee_size: u16,
+    ) -> Result<()> {
+        instructions::handle_update_config(ctx, registration_mode, max_committee_size)
+    }
+
+    /// Require every oracle registered from now on to bond `min_bond` tokens of `bond_mint`,
+    /// held in the program's bond vault. Bonding can only be enabled once.
+    pub fn configure_bonding(
+        ctx: Context<ConfigureBonding>,
+        min_bond: u64,              // Tokens an oracle must bond to register and join committees
+        unbonding_delay_slots: u64, // Slots between an unbond request and its withdrawal
+    ) -> Result<()> {
+        instructions::handle_configure_bonding(ctx, min_bond, unbonding_delay_slots)
+    }
+
+    /// Change the minimum bond and the unbonding delay. Pending unbond requests keep their release slot.
+    pub fn set_bond_parameters(ctx: Context<UpdateConfig>, min_bond: u64, unbonding_delay_slots: u64) -> Result<()> {
+        instructions::handle_set_bond_parameters(ctx, min_bond, unbonding_delay_slots)
+    }
+
+    /// Set the lamports a relayer bonds on each optimistic report and how many slots it can be
+    /// disputed for, enabling optimistic reports. Both must be greater than zero, so a posted
+    /// report always risks a bond and can always be disputed.
+    pub fn set_optimistic_parameters(
+        ctx: Context<UpdateConfig>,
+        optimistic_bond: u64,      // Lamports forfeited to a successful challenger
+        dispute_window_slots: u64, // Slots before an unchallenged report becomes final
+    ) -> Result<()> {
+        instructions::handle_set_optimistic_parameters(ctx, optimistic_bond, dispute_window_slots)
+    }
+
+    /// Pause or unpause every oracle and verification instruction.
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        instructions::handle_set_paused(ctx, paused)
+    }
+
+    /// Let `committee` authorize governance actions with `execute_governance`. From then on the
+    /// changes governance can make, including choosing the next governance committee, are no
+    /// longer available to the admin.
+    pub fn set_governance_committee(ctx: Context<SetGovernanceCommittee>) -> Result<()> {
+        instructions::handle_set_governance_committee(ctx)
+    }
+
+    /// Apply a governance action signed by the governance committee over `governance_message`.
+    /// Proposals must carry the next governance nonce and execute no later than their expiry slot;
+    /// the nonce is consumed on success so a signed proposal runs at most once. Committees other
+    /// than the governance committee are passed as `target_committee`, and feed actions need the
+    /// feed registry.
+    pub fn execute_governance(
+        ctx: Context<ExecuteGovernance>,
+        epoch: u64,                             // Epoch of the governance committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Signature over `governance_message`
+        proposal: GovernanceProposal,           // Action, nonce and expiry
+    ) -> Result<()> {
+        instructions::handle_execute_governance(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, proposal)
+    }
+
+    /// First step of an admin transfer: nominate the new admin.
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        instructions::handle_propose_admin(ctx, new_admin)
+    }
+
+    /// Second step of an admin transfer: the nominated admin accepts.
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::handle_accept_admin(ctx)
+    }
+
+    /// Allow an authority to register an oracle while the registry is in allowlist mode.
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, authority: Pubkey) -> Result<()> {
+        instructions::handle_add_to_allowlist(ctx, authority)
+    }
+
+    /// Remove an authority from the allowlist, refunding the rent to the admin.
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        instructions::handle_remove_from_allowlist(ctx)
+    }
+
+    /// Initialize an Oracle account by storing its compressed G2 public key. The key must come
+    /// with a proof of possession, since committee keys are summed to verify one shared message.
+    pub fn initialize_oracle(
+        ctx: Context<InitializeOracle>,
+        g2_point_key: [u8; 128], // G2 public key of the oracle
+        pop_signature: [u8; 32], // Signature over `proof_of_possession_message(g2_point_key)`
+    ) -> Result<()> {
+        instructions::handle_initialize_oracle(ctx, g2_point_key, pop_signature)
+    }
+
+    /// Deregister an Oracle, closing its account and refunding the rent to its authority.
+    /// If the oracle belongs to a committee, that committee must be supplied and the oracle is
+    /// removed from it. A committee left with fewer members than its threshold can no longer verify.
+    /// Jailed oracles cannot deregister, since registering again would start them unjailed.
+    pub fn deregister_oracle(ctx: Context<DeregisterOracle>) -> Result<()> {
+        instructions::handle_deregister_oracle(ctx)
+    }
+
+    /// Add `amount` tokens to an oracle's bond.
+    pub fn bond(ctx: Context<ManageBond>, amount: u64) -> Result<()> {
+        instructions::handle_bond(ctx, amount)
+    }
+
+    /// Queue `amount` bonded tokens for withdrawal after the unbonding delay. The tokens stop
+    /// counting towards the oracle's weight at the next committee formation or rotation. While
+    /// the oracle sits in its committee's current or still usable previous epoch, the bond
+    /// cannot drop below the minimum; the committee must be supplied when the oracle is bound to one.
+    /// A jailed oracle's remaining bond stays locked.
+    pub fn request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+        instructions::handle_request_unbond(ctx, amount)
+    }
+
+    /// Withdraw every unbond request whose delay has passed to the authority's token account.
+    /// Requests queued before the oracle was jailed stay locked with its bond.
+    pub fn withdraw_unbonded(ctx: Context<ManageBond>) -> Result<()> {
+        instructions::handle_withdraw_unbonded(ctx)
+    }
+
+    /// Set the share of a slashed oracle's bond that is taken and the part of it paid to the reporter.
+    /// The rest goes to `treasury`, a token account of the bond token.
+    pub fn set_slashing_parameters(
+        ctx: Context<SetSlashingParameters>,
+        slash_bps: u16,           // Share of the bond slashed for equivocation
+        reporter_reward_bps: u16, // Share of the slashed amount paid to the reporter
+    ) -> Result<()> {
+        instructions::handle_set_slashing_parameters(ctx, slash_bps, reporter_reward_bps)
+    }
+
+    /// Slash and jail an oracle that signed two different reports for the same feed and round.
+    /// Anyone may submit the evidence; both signatures are checked against the oracle's G2 key
+    /// over `price_report_message`, so no other signed message can be passed off as a report.
+    pub fn slash_equivocation(
+        ctx: Context<SlashEquivocation>,
+        report_a: Vec<u8>,       // Borsh-encoded `PriceReport`
+        signature_a: [u8; 32],   // Oracle's signature over `price_report_message(report_a)`
+        report_b: Vec<u8>,       // Conflicting Borsh-encoded `PriceReport`
+        signature_b: [u8; 32],   // Oracle's signature over `price_report_message(report_b)`
+    ) -> Result<()> {
+        instructions::handle_slash_equivocation(ctx, report_a, signature_a, report_b, signature_b)
+    }
+
+    /// Set the share of a committee's bonded weight, in basis points, that must sign on top of
+    /// the signer threshold. Applies to the current epoch and is carried over by rotations.
+    pub fn set_weight_quorum(ctx: Context<UpdateCommittee>, quorum_bps: u16) -> Result<()> {
+        instructions::handle_set_weight_quorum(ctx, quorum_bps)
+    }
+
+    /// Create a committee at epoch 0 from the oracle accounts passed as remaining accounts.
+    pub fn initialize_committee(
+        ctx: Context<InitializeCommittee>,
+        committee_id: u64,         // Identifier of the committee within the registry
+        threshold: u16,            // Minimum number of signers for a valid committee signature
+        handoff_window_slots: u64, // How long the previous epoch stays usable after a rotation
+        snapshot_retention_epochs: u64, // How many epochs a snapshot is kept before it may be closed
+    ) -> Result<()> {
+        instructions::handle_initialize_committee(ctx, committee_id, threshold, handoff_window_slots, snapshot_retention_epochs)
+    }
+
+    /// Move a committee to the next epoch. The current committee must sign `rotation_message`
+    /// for the next key set, which is passed as remaining oracle accounts, and its signed weights.
+    /// A weight may not exceed the oracle's bond plus delegations when the rotation executes, so
+    /// stake added after signing does not invalidate the signature.
+    pub fn rotate_committee(
+        ctx: Context<RotateCommittee>,
+        threshold: u16,                         // Threshold of the next committee
+        weights: Vec<u64>,                      // Signed weight of each next member, in account order
+        signer_bitmap: u32,                     // Members of the current committee that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature of the signers
+    ) -> Result<()> {
+        instructions::handle_rotate_committee(ctx, threshold, weights, signer_bitmap, aggregated_and_compressed_g1_signature)
+    }
+
+    /// Change how long the previous epoch stays usable after a rotation.
+    pub fn set_handoff_window(ctx: Context<UpdateCommittee>, handoff_window_slots: u64) -> Result<()> {
+        instructions::handle_set_handoff_window(ctx, handoff_window_slots)
+    }
+
+    /// Change the timestamp limits applied to timestamped messages signed by the committee.
+    /// A limit of 0 disables that check.
+    pub fn set_timestamp_limits(
+        ctx: Context<UpdateCommittee>,
+        max_staleness: i64,   // Oldest accepted message age in seconds
+        max_future_skew: i64, // Furthest accepted message time ahead of the clock in seconds
+    ) -> Result<()> {
+        instructions::handle_set_timestamp_limits(ctx, max_staleness, max_future_skew)
+    }
+
+    /// Verify a committee signature over `timestamped_message(envelope)`, rejecting it if the
+    /// envelope's timestamp is outside the committee's staleness and skew limits.
+    pub fn verify_timestamped_signature(
+        ctx: Context<VerifyCommitteeSignature>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        envelope: Vec<u8>,                      // Borsh-encoded `TimestampedMessage`
+    ) -> Result<()> {
+        instructions::handle_verify_timestamped_signature(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, envelope)
+    }
+
+    /// Change how many epochs a snapshot is kept before it may be closed.
+    pub fn set_snapshot_retention(ctx: Context<UpdateCommittee>, snapshot_retention_epochs: u64) -> Result<()> {
+        instructions::handle_set_snapshot_retention(ctx, snapshot_retention_epochs)
+    }
+
+    /// Record an immutable snapshot of the committee's member set for `epoch`, which must be the
+    /// current or the previous epoch. Anyone may pay for a snapshot and gets the rent back on close.
+    pub fn snapshot_committee(ctx: Context<SnapshotCommittee>, epoch: u64) -> Result<()> {
+        instructions::handle_snapshot_committee(ctx, epoch)
+    }
+
+    /// Close a snapshot once it is older than the committee's retention policy, refunding the
+    /// rent to whoever paid for it.
+    pub fn close_snapshot(ctx: Context<CloseSnapshot>) -> Result<()> {
+        instructions::handle_close_snapshot(ctx)
+    }
+
+    /// Verify a signature over `application_message(message)` against a committee snapshot, for
+    /// attestations made in an older epoch.
+    pub fn verify_snapshot_signature(
+        ctx: Context<VerifySnapshotSignature>,
+        signer_bitmap: u32,                     // Members of the snapshot that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        message: Vec<u8>,                       // The message that was signed
+    ) -> Result<()> {
+        instructions::handle_verify_snapshot_signature(ctx, signer_bitmap, aggregated_and_compressed_g1_signature, message)
+    }
+
+    /// Verify a signature over `application_message(message)` by the committee of `epoch`, which
+    /// must be the current epoch or the previous one while its handoff window is open.
+    pub fn verify_committee_signature(
+        ctx: Context<VerifyCommitteeSignature>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        message: Vec<u8>,                       // The message that was signed
+    ) -> Result<()> {
+        instructions::handle_verify_committee_signature(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, message)
+    }
 
-    /// Verify the aggregated signature against the aggregated public key and message.
-    pub fn verify_aggregated_signature(
+    /// Verify the aggregated signature over `application_message(message)` using the oracles' G2
+    /// compressed public keys.
+    pub fn verify_signature(
         ctx: Context<VerifySignature>,
-        aggregated_compressed_pubkey: [u8; 64], // Aggregated and compressed G2 public key
-        aggregated_compressed_signature: [u8; 32], // Aggregated and compressed G1 signature
-        message: Vec<u8>, // The message that was signed
-    ) -> Result<()> {
-        let aggregated_pubkey = G2CompressedPoint(aggregated_compressed_pubkey); // Load the compressed G2 public key
-        let aggregated_signature = G1CompressedPoint(aggregated_compressed_signature); // Load the compressed G1 signature
-        sol_log_compute_units();
-        // Verify the aggregated signature
-        aggregated_pubkey
-            .verify_signature::<Sha256Normalized, &[u8], G1CompressedPoint>(
-                aggregated_signature,
-                &message,
-            )
-            .map_err(|_| {
-                msg!("Signature verification failed.");
-                error!(ErrorCode::SignatureVerificationFailed)
-            })?;
-        sol_log_compute_units();
-
-        msg!("✅ Aggregated signature verified successfully!");
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        message: Vec<u8>,                                 // The message that was signed
+    ) -> Result<()> {
+        instructions::handle_verify_signature(ctx, aggregated_and_compressed_g1_signature, message)
+    }
+
+    /// Create a committee stored as a Merkle root over weighted G2 keys. Keys cannot be checked
+    /// on-chain here, so the admin must check each one's proof of possession before committing
+    /// it to the root.
+    pub fn initialize_merkle_committee(
+        ctx: Context<InitializeMerkleCommittee>,
+        committee_id: u64,   // Identifier of the committee within the registry
+        root: [u8; 32],      // Merkle root over `merkle_leaf(key, weight)` of every member
+        member_count: u32,   // Number of leaves in the tree
+        total_weight: u64,   // Sum of every member's weight
+        quorum_weight: u64,  // Minimum signing weight for a valid committee signature
+    ) -> Result<()> {
+        instructions::handle_initialize_merkle_committee(ctx, committee_id, root, member_count, total_weight, quorum_weight)
+    }
+
+    /// Replace the member tree of a Merkle committee.
+    pub fn update_merkle_committee(
+        ctx: Context<UpdateMerkleCommittee>,
+        root: [u8; 32],
+        member_count: u32,
+        total_weight: u64,
+        quorum_weight: u64,
+    ) -> Result<()> {
+        instructions::handle_update_merkle_committee(ctx, root, member_count, total_weight, quorum_weight)
+    }
+
+    /// Create a buffer to upload a `MerkleSignerProof` too large for one transaction.
+    pub fn init_proof_buffer(ctx: Context<InitProofBuffer>, _buffer_id: u64, capacity: u32) -> Result<()> {
+        instructions::handle_init_proof_buffer(ctx, capacity)
+    }
+
+    /// Write a chunk of a serialized `MerkleSignerProof` into a proof buffer at `offset`.
+    pub fn write_proof_buffer(ctx: Context<WriteProofBuffer>, offset: u32, bytes: Vec<u8>) -> Result<()> {
+        instructions::handle_write_proof_buffer(ctx, offset, bytes)
+    }
+
+    /// Close a proof buffer, refunding the rent to its authority.
+    pub fn close_proof_buffer(_ctx: Context<CloseProofBuffer>) -> Result<()> {
         Ok(())
     }
-}
 
+    /// Verify a signature over `application_message(message)` by the members of a Merkle committee.
+    /// The participating keys and their multiproof come either from `signer_proof` or from a proof
+    /// buffer account, not both.
+    pub fn verify_merkle_signature(
+        ctx: Context<VerifyMerkleSignature>,
+        signer_proof: Option<MerkleSignerProof>, // Participating keys and multiproof, if not buffered
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        message: Vec<u8>,                        // The message that was signed
+    ) -> Result<()> {
+        instructions::handle_verify_merkle_signature(ctx, signer_proof, aggregated_and_compressed_g1_signature, message)
+    }
 
-/// Accounts structure for verifying a signature.
-#[derive(Accounts)]
-pub struct VerifySignature {}
+    /// Create a paged registry. Members are added to pages created with `add_registry_page`.
+    pub fn initialize_paged_registry(
+        ctx: Context<InitializePagedRegistry>,
+        registry_id: u64,   // Identifier of the registry within the program config
+        quorum_weight: u64, // Minimum signing weight for a valid signature
+    ) -> Result<()> {
+        instructions::handle_initialize_paged_registry(ctx, registry_id, quorum_weight)
+    }
 
-/// Custom error codes for the program.
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Signature verification failed.")]
-    SignatureVerificationFailed,
-}
+    /// Change the signing weight a paged registry requires.
+    pub fn set_registry_quorum(ctx: Context<UpdatePagedRegistry>, quorum_weight: u64) -> Result<()> {
+        instructions::handle_set_registry_quorum(ctx, quorum_weight)
+    }
+
+    /// Append an empty page to a paged registry.
+    pub fn add_registry_page(ctx: Context<AddRegistryPage>) -> Result<()> {
+        instructions::handle_add_registry_page(ctx)
+    }
+
+    /// Append a member to the first free slot at the end of a page. Like oracle registration,
+    /// the key must come with a proof of possession.
+    pub fn append_member(
+        ctx: Context<UpdateRegistryPage>,
+        g2_point_key: [u8; 128],
+        weight: u64,
+        pop_signature: [u8; 32],
+    ) -> Result<()> {
+        instructions::handle_append_member(ctx, g2_point_key, weight, pop_signature)
+    }
+
+    /// Mark the member in `slot` of a page as removed. Its index stays reserved until the page is compacted.
+    pub fn remove_member(ctx: Context<UpdateRegistryPage>, slot: u32) -> Result<()> {
+        instructions::handle_remove_member(ctx, slot)
+    }
+
+    /// Move the active members of a page to its front, freeing the slots of removed members.
+    /// Indices of members after a removed slot change, so signers must re-read them.
+    pub fn compact_page(ctx: Context<UpdateRegistryPage>) -> Result<()> {
+        instructions::handle_compact_page(ctx)
+    }
+
+    /// Verify a signature over `application_message(message)` by registry members referenced by
+    /// index (`page_index * PAGE_CAPACITY + slot`).
+    /// Indices must be strictly increasing and the pages they fall in passed as remaining accounts
+    /// in ascending page order.
+    pub fn verify_paged_signature<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyPagedSignature<'info>>,
+        signer_indices: Vec<u32>,                // Indices of the members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        message: Vec<u8>,                        // The message that was signed
+    ) -> Result<()> {
+        instructions::handle_verify_paged_signature(ctx, signer_indices, aggregated_and_compressed_g1_signature, message)
+    }
+
+    /// Start a verification that spans several transactions. The message hash and signature are
+    /// fixed here; signers are added with `accumulate_session_signers` and checked by
+    /// `finalize_verification_session`. Only the owner can continue the session.
+    pub fn begin_verification_session(
+        ctx: Context<BeginVerificationSession>,
+        _session_id: u64,                        // Distinguishes concurrent sessions of one owner
+        message_hash: [u8; 32],                  // SHA-256 of the message that was signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+    ) -> Result<()> {
+        instructions::handle_begin_verification_session(ctx, message_hash, aggregated_and_compressed_g1_signature)
+    }
+
+    /// Add a batch of signers to a session's partial aggregate. Indices must be strictly increasing
+    /// and past every index added before, so no member can be counted twice.
+    pub fn accumulate_session_signers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AccumulateSessionSigners<'info>>,
+        signer_indices: Vec<u32>, // Indices of the next members that signed
+    ) -> Result<()> {
+        instructions::handle_accumulate_session_signers(ctx, signer_indices)
+    }
+
+    /// Run the pairing check over a session's accumulated signers, who signed
+    /// `application_message(message)`, and mark it verified.
+    pub fn finalize_verification_session(
+        ctx: Context<FinalizeVerificationSession>,
+        message: Vec<u8>, // The message that was signed, must match the session's hash
+    ) -> Result<()> {
+        instructions::handle_finalize_verification_session(ctx, message)
+    }
+
+    /// Close a verification session, refunding the rent to its owner.
+    pub fn close_verification_session(_ctx: Context<CloseVerificationSession>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Create the registry holding the policy of every price feed.
+    pub fn initialize_feed_registry(ctx: Context<InitializeFeedRegistry>) -> Result<()> {
+        instructions::handle_initialize_feed_registry(ctx)
+    }
+
+    /// Create a price feed updated by reports signed by `committee`, registering its policy.
+    pub fn create_price_feed(
+        ctx: Context<CreatePriceFeed>,
+        feed_id: [u8; 32],      // Identifier signed into every report
+        settings: FeedSettings, // Quorum, exponent, staleness and update policy
+    ) -> Result<()> {
+        instructions::handle_create_price_feed(ctx, feed_id, settings)
+    }
+
+    /// Create the feed and history accounts of a feed registered by governance. Anyone may pay.
+    pub fn open_price_feed(ctx: Context<OpenPriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::handle_open_price_feed(ctx, feed_id)
+    }
 
+    /// Replace the policy of a feed, binding it to `committee`.
+    pub fn update_feed_policy(ctx: Context<UpdateFeedPolicy>, feed_id: [u8; 32], settings: FeedSettings) -> Result<()> {
+        instructions::handle_update_feed_policy(ctx, feed_id, settings)
+    }
+
+    /// Retire a feed. Updates are rejected from then on and its price should no longer be read.
+    pub fn retire_feed(ctx: Context<RetireFeed>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::handle_retire_feed(ctx, feed_id)
+    }
+
+    /// Freeze feeds after their committee signed two different reports for the same feed and
+    /// round. Both signatures are checked against the committee. Only the reports' feed is frozen
+    /// unless `freeze_committee` is set, in which case the committee and every feed it updates
+    /// are frozen.
+    pub fn report_committee_equivocation(
+        ctx: Context<ReportCommitteeEquivocation>,
+        report_a: CommitteeSignedReport, // First report and the committee's signature over it
+        report_b: CommitteeSignedReport, // Conflicting report and the committee's signature over it
+        freeze_committee: bool,          // Freeze the committee and all of its feeds
+    ) -> Result<()> {
+        instructions::handle_report_committee_equivocation(ctx, report_a, report_b, freeze_committee)
+    }
+
+    /// Unfreeze a feed after review. Its committee must be unfrozen separately if it was frozen.
+    pub fn unfreeze_feed(ctx: Context<UnfreezeFeed>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::handle_unfreeze_feed(ctx, feed_id)
+    }
+
+    /// Unfreeze a committee after review. Its feeds stay frozen until each is unfrozen.
+    pub fn unfreeze_committee(ctx: Context<UpdateCommittee>) -> Result<()> {
+        instructions::handle_unfreeze_committee(ctx)
+    }
+
+    /// Store a price report signed by the feed's committee over `price_report_message(report)`.
+    /// `report` is the Borsh encoding of a `PriceReport`; it is decoded after the signature check
+    /// and stored only if it is newer than the stored report.
+    pub fn update_price(
+        ctx: Context<UpdatePrice>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        report: Vec<u8>,                        // Borsh-encoded `PriceReport`
+    ) -> Result<()> {
+        instructions::handle_update_price(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, report)
+    }
+
+    /// Record a Merkle root over a batch of Borsh-encoded `PriceReport` leaves, signed once by
+    /// the committee. Feeds are then updated from the root with `update_price_from_root`.
+    pub fn attest_price_root(
+        ctx: Context<AttestPriceRoot>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Signature over `price_root_message`
+        root: [u8; 32],                         // Root of the report tree
+    ) -> Result<()> {
+        instructions::handle_attest_price_root(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, root)
+    }
+
+    /// Update a feed from a report in an attested root. Only the Merkle proof is checked; the
+    /// root's signature was verified when it was attested.
+    pub fn update_price_from_root(
+        ctx: Context<UpdatePriceFromRoot>,
+        report: Vec<u8>,       // Borsh-encoded `PriceReport` leaf
+        proof: Vec<[u8; 32]>,  // Sibling hashes from the leaf up to the root
+    ) -> Result<()> {
+        instructions::handle_update_price_from_root(ctx, report, proof)
+    }
+
+    /// Close an attested root and refund its rent. Feeds not yet updated from it can no longer be.
+    pub fn close_attested_root(_ctx: Context<CloseAttestedRoot>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Update a price feed from reports each member signed separately, over
+    /// `observation_message(oracle, observation)`. The signatures are aggregated into one and
+    /// checked with a single multi-pairing; the feed stores the median price and confidence,
+    /// stamped with the oldest publish time among the reports, along with the observed price range.
+    pub fn update_price_median(
+        ctx: Context<UpdatePrice>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed, in member order
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregate of the members' signatures
+        observations: Vec<Vec<u8>>,             // Borsh-encoded `PriceReport` per signer, in bitmap order
+    ) -> Result<()> {
+        instructions::handle_update_price_median(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, observations)
+    }
+
+    /// Return the time-weighted average price of a feed over the last `window` seconds. The
+    /// result is also set as return data so other programs can read it through CPI. Retired and
+    /// frozen feeds cannot be read.
+    pub fn get_twap(ctx: Context<ReadPriceHistory>, window: i64) -> Result<i64> {
+        instructions::handle_get_twap(ctx, window)
+    }
+
+    /// Create the pool oracles are paid from. Each update verified with a signer bitmap pays
+    /// `fee_per_update` lamports out of the funded balance, split evenly among the signers.
+    pub fn initialize_reward_pool(ctx: Context<InitializeRewardPool>, fee_per_update: u64) -> Result<()> {
+        instructions::handle_initialize_reward_pool(ctx, fee_per_update)
+    }
+
+    /// Change the amount paid out per verified update.
+    pub fn set_update_fee(ctx: Context<SetUpdateFee>, fee_per_update: u64) -> Result<()> {
+        instructions::handle_set_update_fee(ctx, fee_per_update)
+    }
+
+    /// Pay `amount` lamports into the reward pool, prepaying future updates.
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        instructions::handle_fund_rewards(ctx, amount)
+    }
+
+    /// Pay the operator's share of an oracle's accrued rewards to its authority. The delegators'
+    /// share stays in the pool until each of them claims it.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::handle_claim_rewards(ctx)
+    }
+
+    /// Set the share of delegators' rewards the operator keeps. Rewards accrued so far are split
+    /// at the old commission first.
+    pub fn set_commission(ctx: Context<SetCommission>, commission_bps: u16) -> Result<()> {
+        instructions::handle_set_commission(ctx, commission_bps)
+    }
+
+    /// Open or close an oracle to new delegations. Existing delegations are unaffected.
+    pub fn set_accepts_delegations(ctx: Context<SetCommission>, accepts_delegations: bool) -> Result<()> {
+        instructions::handle_set_accepts_delegations(ctx, accepts_delegations)
+    }
+
+    /// Delegate `amount` bond tokens to an oracle that accepts delegations. Delegated tokens add
+    /// to the oracle's weight from the next committee formation or rotation, and earn a share of
+    /// its rewards from the first epoch that counts them. Pending rewards of an existing
+    /// delegation are paid out first.
+    pub fn delegate(ctx: Context<Delegate>, amount: u64) -> Result<()> {
+        instructions::handle_delegate(ctx, amount)
+    }
+
+    /// Queue `amount` delegated tokens for withdrawal after the unbonding delay, paying out
+    /// pending rewards. The tokens stop counting towards the oracle's weight at the next committee
+    /// formation or rotation, and stop earning rewards immediately.
+    pub fn undelegate(ctx: Context<ManageDelegation>, amount: u64) -> Result<()> {
+        instructions::handle_undelegate(ctx, amount)
+    }
+
+    /// Queue all of a delegation's tokens for withdrawal after the unbonding delay, paying out
+    /// pending rewards. Lets the oracle's authority clear delegations it no longer wants, such as
+    /// before deregistering, which a jailed oracle cannot do. Delegators can still undelegate.
+    pub fn release_delegation(ctx: Context<ReleaseDelegation>) -> Result<()> {
+        instructions::handle_release_delegation(ctx)
+    }
+
+    /// Pay a delegator the rewards its delegation has earned.
+    pub fn claim_delegation_rewards(ctx: Context<ManageDelegation>) -> Result<()> {
+        instructions::handle_claim_delegation_rewards(ctx)
+    }
+
+    /// Withdraw every undelegation whose delay has passed to the delegator's token account,
+    /// closing the delegation once nothing is left in it.
+    pub fn withdraw_undelegated(ctx: Context<WithdrawUndelegated>) -> Result<()> {
+        instructions::handle_withdraw_undelegated(ctx)
+    }
+
+    /// Post an unsigned report for a feed, bonding `optimistic_bond` lamports. Unless a committee
+    /// attestation of a conflicting report is submitted within the dispute window, the report
+    /// becomes final and readable through `PriceFeed::get_optimistic_price`.
+    pub fn post_optimistic_report(ctx: Context<PostOptimisticReport>, report: PriceReport) -> Result<()> {
+        instructions::handle_post_optimistic_report(ctx, report)
+    }
+
+    /// Dispute an optimistic report with a report for the same feed and round at a different price,
+    /// signed by the committee over `price_report_message`. The claim is closed and its bond and rent paid to the challenger.
+    pub fn challenge_optimistic_report(
+        ctx: Context<ChallengeOptimisticReport>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+        report: Vec<u8>,                        // Borsh-encoded conflicting `PriceReport`
+    ) -> Result<()> {
+        instructions::handle_challenge_optimistic_report(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, report)
+    }
+
+    /// Finalize an optimistic report once its dispute window has passed, storing it as the feed's
+    /// optimistic price if it is still the newest, and refund the bond and rent to the poster.
+    /// Reports for frozen feeds stay open until the feed is unfrozen; those for retired feeds are
+    /// refunded without being stored.
+    pub fn finalize_optimistic_report(ctx: Context<FinalizeOptimisticReport>) -> Result<()> {
+        instructions::handle_finalize_optimistic_report(ctx)
+    }
+
+    /// Create the vault spent by `committee` signatures. SOL is deposited by transferring to the
+    /// vault address and SPL tokens by holding them in token accounts owned by it.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        instructions::handle_initialize_vault(ctx)
+    }
+
+    /// Execute a transfer out of a committee vault, signed by the committee over
+    /// `vault_transfer_message`. Intents must move a nonzero amount, carry the vault's next nonce
+    /// and execute no later than their expiry slot. SOL transfers pay `destination` directly and keep the vault rent
+    /// exempt; SPL transfers move tokens from `vault_token_account` to the token account
+    /// `destination`.
+    pub fn execute_vault_transfer(
+        ctx: Context<ExecuteVaultTransfer>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Signature over `vault_transfer_message`
+        intent: TransferIntent,                 // Transfer to execute
+    ) -> Result<()> {
+        instructions::handle_execute_vault_transfer(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, intent)
+    }
+
+    /// Create a bridge from `source_chain_id` whose deposits are attested by `committee`, with a
+    /// wrapped token minted by the bridge PDA.
+    pub fn initialize_bridge(ctx: Context<InitializeBridge>, source_chain_id: u64, decimals: u8) -> Result<()> {
+        instructions::handle_initialize_bridge(ctx, source_chain_id, decimals)
+    }
+
+    /// Mint wrapped tokens for a deposit on the source chain, attested by the bridge committee over
+    /// `bridge_deposit_message`. Deposits must be for a nonzero amount, and each is marked
+    /// consumed so it can be claimed only once.
+    pub fn claim(
+        ctx: Context<Claim>,
+        epoch: u64,                             // Epoch of the committee that signed
+        signer_bitmap: u32,                     // Members that signed
+        aggregated_and_compressed_g1_signature: [u8; 32], // Signature over `bridge_deposit_message`
+        deposit: DepositAttestation,            // Deposit on the source chain
+    ) -> Result<()> {
+        instructions::handle_claim(ctx, epoch, signer_bitmap, aggregated_and_compressed_g1_signature, deposit)
+    }
+
+    /// Burn wrapped tokens to release them to `recipient` on the source chain. The emitted
+    /// `BridgeBurned` event carries the outbound nonce the committee attests on the other side.
+    pub fn burn_for_bridge(ctx: Context<BurnForBridge>, amount: u64, recipient: [u8; 32]) -> Result<()> {
+        instructions::handle_burn_for_bridge(ctx, amount, recipient)
+    }
+}
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use anchor_lang::prelude::*;
     use solana_program_test::*;
     use solana_sdk::{
+        signature::Keypair,
         signer::Signer,
         transaction::Transaction,
         pubkey::Pubkey,
-        instruction::Instruction,
+        instruction::{Instruction, InstructionError},
+        transaction::TransactionError,
     };
-    use utils::BlsUtils;
-    use crate::program;
-    use solana_alt_bn128_bls::{G1CompressedPoint, PrivKey, G2CompressedPoint, G2Point};
+    use solana_alt_bn128_bls::{G1CompressedPoint, PrivKey};
     use anchor_lang::InstructionData;
-    use std::str::FromStr;
     use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use anchor_lang::solana_program::entrypoint::ProgramResult;
+    use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+    use solana_sdk::account::AccountSharedData;
+    use utils::BlsUtils;
 
-    #[tokio::test]
-    async fn test_aggregated_verification_off_chain() {
-         // Step 1: Set up the Solana test environment
-         let program_id = Pubkey::from_str("E4LXwvzGcZzdJfURbdLDW2BLqz73JLq9ncBd6NteYik").unwrap();
-         let mut program_test = ProgramTest::new(
-             "latest_bls", 
-             program_id, 
-             None,
-         );
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    /// Run the program's entrypoint natively so the tests do not depend on a prebuilt `.so`.
+    fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        let accounts = Box::leak(Box::new(accounts.to_vec()));
+        entry(program_id, accounts, data)
+    }
 
-        // Step 1: Generate private keys and derive G2 points
-        let private_keys: Vec<PrivKey> = (0..5)
-            .map(|_| PrivKey::from_random())
-            .collect();
-        let g2_points: Vec<G2Point> = private_keys
-            .iter()
-            .map(|key| G2Point::try_from(key).expect("Failed to derive G2 point from private key"))
-            .collect();
-    
-        // Step 2: Aggregate G2 points
-        // let mut aggregated_g2_point = g2_points[0].clone(); // Start with the first valid G2 point
-        // for g2_point in g2_points.iter().skip(1) {
-        //     aggregated_g2_point = aggregated_g2_point + g2_point.clone();
-        // }
-        // // Compress aggregated G2 point
-        // let aggregated_compressed_pubkey =
-        //     G2CompressedPoint::try_from(&aggregated_g2_point).expect("Failed to compress G2 point");
-        let aggregated_compressed_pubkey = BlsUtils::aggregate_and_compress_g2_points(&g2_points).expect("Failed to aggregate and compress G2 points");
-    
-        // Step 3: Generate an aggregated G1 signature (off-chain)
-        let message = b"500000.23456".to_vec();
-        let g1_signatures: Vec<G1Point> = private_keys
-            .iter()
-            .map(|key| {
-                key.sign::<Sha256Normalized, &[u8]>(&message)
-                    .expect("Failed to sign message with private key")
-            })
-            .collect();
-    
-        // let mut aggregated_g1_signature = g1_signatures[0].clone(); // Start with the first valid G1 signature
-        // for g1_signature in g1_signatures.iter().skip(1) {
-        //     aggregated_g1_signature = aggregated_g1_signature + g1_signature.clone();
-        // }
-    
-        // // Compress aggregated G1 signature
-        // let aggregated_compressed_signature =
-        //     G1CompressedPoint::try_from(aggregated_g1_signature).expect("Failed to compress G1 signature");
-        let aggregated_compressed_signature = BlsUtils::aggregate_and_compress_g1_points(&g1_signatures).expect("Failed to aggregate and compress G1 points");
-        
-        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
-        let instruction = Instruction {
-                program_id,
-                accounts: vec![],
-                data: crate::instruction::VerifyAggregatedSignature {
-                    aggregated_compressed_pubkey: aggregated_compressed_pubkey,
-                    aggregated_compressed_signature: aggregated_compressed_signature,
-                    message: message.clone(),
-                }
-                .data(),
+    /// Derive the program's ProgramData address under the upgradeable loader.
+    fn program_data_address() -> Pubkey {
+        Pubkey::find_program_address(&[crate::ID.as_ref()], &bpf_loader_upgradeable::id()).0
+    }
+
+    /// Start the program with a ProgramData account naming the payer as upgrade authority, so
+    /// the payer may create the config. The context must outlive the test's transactions.
+    async fn start_program() -> ProgramTestContext {
+        let program_test = ProgramTest::new("latest_bls", crate::ID, processor!(process_instruction));
+        let mut context = program_test.start_with_context().await;
+        let state = UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(context.payer.pubkey()),
         };
-        
+        // Fund the account from the payer so the bank's capitalization stays consistent
+        let lamports = Rent::default().minimum_balance(UpgradeableLoaderState::size_of_programdata_metadata());
+        let payer = context.payer.pubkey();
+        let mut payer_account = context.banks_client.get_account(payer).await.unwrap().unwrap();
+        payer_account.lamports -= lamports;
+        context.set_account(&payer, &payer_account.into());
+        let account = AccountSharedData::new_data(lamports, &state, &bpf_loader_upgradeable::id()).unwrap();
+        context.set_account(&program_data_address(), &account);
+        context
+    }
+
+    /// Assert that a transaction failed with `code` from this program.
+    fn assert_error(result: std::result::Result<(), BanksClientError>, code: ErrorCode) {
+        match result {
+            Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(actual)))) => {
+                assert_eq!(actual, u32::from(code), "unexpected error code")
+            }
+            other => panic!("expected {:?}, got {:?}", code, other),
+        }
+    }
+
+    /// Derive the program configuration PDA.
+    fn config_address(program_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[CONFIG_SEED], &program_id).0
+    }
+
+    /// Build the instruction creating the program configuration with `admin` as admin.
+    fn initialize_config_instruction(
+        program_id: Pubkey,
+        admin: Pubkey,
+        registration_mode: RegistrationMode,
+    ) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(config_address(program_id), false),
+                AccountMeta::new(admin, true),
+                AccountMeta::new_readonly(program_data_address(), false),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            ],
+            data: instruction::InitializeConfig {
+                registration_mode,
+                max_committee_size: 16,
+            }
+            .data(),
+        }
+    }
+
+    /// Build the instruction registering `private_key` as an oracle at `oracle` for `authority`.
+    fn initialize_oracle_instruction(oracle: Pubkey, authority: Pubkey, private_key: &PrivKey) -> Instruction {
+        let g2_point_key = G2Point::try_from(private_key).expect("Invalid private key");
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(oracle, false),
+                AccountMeta::new(authority, true),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                AccountMeta::new_readonly(config_address(crate::ID), false),
+            ],
+            data: instruction::InitializeOracle {
+                g2_point_key: g2_point_key.0,
+                pop_signature: proof_of_possession(private_key),
+            }
+            .data(),
+        }
+    }
+
+    /// Sign the proof-of-possession message for `private_key`'s public key.
+    fn proof_of_possession(private_key: &PrivKey) -> [u8; 32] {
+        let g2_point_key = G2Point::try_from(private_key).expect("Invalid private key");
+        let message = proof_of_possession_message(&g2_point_key.0);
+        sign_aggregate(&[private_key], &message)
+    }
+
+    /// A committee at epoch 0 whose members are freshly registered oracles.
+    struct CommitteeFixture {
+        config: Pubkey,
+        committee: Pubkey,
+        private_keys: Vec<PrivKey>,
+        members: Vec<CommitteeMember>,
+        authorities: Vec<Keypair>,
+    }
+
+    fn feed_registry_address(config: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[FEED_REGISTRY_SEED, config.as_ref()], &crate::ID).0
+    }
+
+    fn reward_pool_address(config: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[REWARD_POOL_SEED, config.as_ref()], &crate::ID).0
+    }
+
+    /// Settings used by the test feeds: an exponent of -5 and loose time limits.
+    fn test_feed_settings() -> FeedSettings {
+        FeedSettings { expo: -5, max_staleness: 3_600, max_future_skew: 60, ..FeedSettings::default() }
+    }
+
+    /// Create a feed bound to `committee`, creating the feed registry first if needed. Returns
+    /// the feed and history addresses.
+    async fn create_price_feed(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: solana_sdk::hash::Hash,
+        config: Pubkey,
+        committee: Pubkey,
+        feed_id: [u8; 32],
+        settings: FeedSettings,
+    ) -> (Pubkey, Pubkey) {
+        let program_id = crate::ID;
+        let feed_registry = feed_registry_address(&config);
+        if banks_client.get_account(feed_registry).await.unwrap().is_none() {
+            let tx = Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(feed_registry, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::InitializeFeedRegistry {}.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[payer],
+                recent_blockhash,
+            );
+            banks_client.process_transaction(tx).await.unwrap();
+        }
+        let feed = Pubkey::find_program_address(&[FEED_SEED, config.as_ref(), &feed_id], &program_id).0;
+        let history = Pubkey::find_program_address(&[HISTORY_SEED, feed.as_ref()], &program_id).0;
         let tx = Transaction::new_signed_with_payer(
-            &[compute_budget_ix, instruction],
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new_readonly(committee, false),
+                    AccountMeta::new(feed_registry, false),
+                    AccountMeta::new(feed, false),
+                    AccountMeta::new(history, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::CreatePriceFeed { feed_id, settings }.data(),
+            }],
             Some(&payer.pubkey()),
-            &[&payer],
+            &[payer],
             recent_blockhash,
         );
+        banks_client.process_transaction(tx).await.unwrap();
+        (feed, history)
+    }
 
-        // Step 7: Send the transaction and verify the result
-        let result = banks_client.process_transaction(tx).await;
-        match result {
-            Ok(_) => println!("✅ Signature verified successfully on-chain!"),
-            Err(err) => panic!("❌ Signature verification failed on-chain: {:?}", err),
+    /// Create the config, register `member_count` oracles and form committee 0 from them.
+    async fn setup_committee(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: solana_sdk::hash::Hash,
+        member_count: usize,
+        threshold: u16,
+    ) -> CommitteeFixture {
+        let program_id = crate::ID;
+        let config = config_address(program_id);
+        let committee = Pubkey::find_program_address(
+            &[COMMITTEE_SEED, config.as_ref(), &0u64.to_le_bytes()],
+            &program_id,
+        )
+        .0;
+
+        // Step 1: Register the oracles, each with its own authority
+        let authorities: Vec<Keypair> = (0..member_count).map(|_| Keypair::new()).collect();
+        let private_keys: Vec<PrivKey> = (0..member_count).map(|_| PrivKey::from_random()).collect();
+        let mut instructions = vec![initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open)];
+        let mut members = Vec::new();
+        for (authority, private_key) in authorities.iter().zip(&private_keys) {
+            let g2_point_key = G2Point::try_from(private_key).expect("Invalid private key");
+            let (oracle, _) = OracleAccount::find_address(&config, &authority.pubkey());
+            instructions.push(solana_sdk::system_instruction::transfer(
+                &payer.pubkey(),
+                &authority.pubkey(),
+                1_000_000_000,
+            ));
+            instructions.push(initialize_oracle_instruction(oracle, authority.pubkey(), private_key));
+            members.push(CommitteeMember { oracle, g2_point_key: g2_point_key.0, weight: 0 });
         }
+        let mut signers: Vec<&Keypair> = vec![payer];
+        signers.extend(authorities.iter());
+        let tx = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &signers, recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 2: Create the committee at epoch 0
+        let mut accounts = vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(committee, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+        ];
+        accounts.extend(members.iter().map(|member| AccountMeta::new(member.oracle, false)));
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts,
+                data: instruction::InitializeCommittee {
+                    committee_id: 0,
+                    threshold,
+                    handoff_window_slots: 1_000,
+                    snapshot_retention_epochs: 4,
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        CommitteeFixture { config, committee, private_keys, members, authorities }
     }
+
+    /// A paged registry with a single page holding the given members, each of weight 1.
+    struct PagedRegistryFixture {
+        config: Pubkey,
+        registry: Pubkey,
+        page: Pubkey,
+    }
+
+    /// Create the config and a paged registry whose first page holds `private_keys`.
+    async fn setup_paged_registry(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: solana_sdk::hash::Hash,
+        private_keys: &[PrivKey],
+        quorum_weight: u64,
+    ) -> PagedRegistryFixture {
+        let program_id = crate::ID;
+        let config = config_address(program_id);
+        let registry = Pubkey::find_program_address(&[REGISTRY_SEED, config.as_ref(), &0u64.to_le_bytes()], &program_id).0;
+        let page = Pubkey::find_program_address(&[REGISTRY_PAGE_SEED, registry.as_ref(), &0u32.to_le_bytes()], &program_id).0;
+        let mut instructions = vec![
+            initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(registry, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::InitializePagedRegistry { registry_id: 0, quorum_weight }.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(registry, false),
+                    AccountMeta::new(page, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::AddRegistryPage {}.data(),
+            },
+        ];
+        instructions.extend(private_keys.iter().map(|private_key| {
+            append_member_instruction(config, registry, page, payer.pubkey(), private_key, proof_of_possession(private_key))
+        }));
+        let tx = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[payer], recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+        PagedRegistryFixture { config, registry, page }
+    }
+
+    /// Build the instruction appending `private_key`'s public key to `page` with weight 1.
+    fn append_member_instruction(
+        config: Pubkey,
+        registry: Pubkey,
+        page: Pubkey,
+        admin: Pubkey,
+        private_key: &PrivKey,
+        pop_signature: [u8; 32],
+    ) -> Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(registry, false),
+                AccountMeta::new(page, false),
+                AccountMeta::new_readonly(admin, true),
+            ],
+            data: instruction::AppendMember {
+                g2_point_key: G2Point::try_from(private_key).expect("Invalid private key").0,
+                weight: 1,
+                pop_signature,
+            }
+            .data(),
+        }
+    }
+
+    /// Sign `message` with every key and aggregate the signatures.
+    fn sign_aggregate(keys: &[&PrivKey], message: &[u8]) -> [u8; 32] {
+        let signatures: Vec<G1Point> = keys
+            .iter()
+            .map(|key| key.sign::<Sha256Normalized, &[u8]>(message).expect("Failed to sign message"))
+            .collect();
+        let aggregated = signatures.into_iter().reduce(|aggregated, signature| aggregated + signature).unwrap();
+        G1CompressedPoint::try_from(aggregated).expect("Failed to compress G1 signature").0
+    }
+
+    /// An `UpdatePrice` instruction for `report` signed by `keys`, the members in `signer_bitmap`.
+    fn update_price_instruction(
+        config: Pubkey,
+        committee: Pubkey,
+        feed: Pubkey,
+        history: Pubkey,
+        keys: &[&PrivKey],
+        signer_bitmap: u32,
+        report: &PriceReport,
+    ) -> Instruction {
+        let report = report.try_to_vec().unwrap();
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new_readonly(feed_registry_address(&config), false),
+                AccountMeta::new(feed, false),
+                AccountMeta::new(history, false),
+                AccountMeta::new_readonly(crate::ID, false), // No reward pool
+            ],
+            data: instruction::UpdatePrice {
+                epoch: 0,
+                signer_bitmap,
+                aggregated_and_compressed_g1_signature: sign_aggregate(keys, &price_report_message(&report)),
+                report,
+            }
+            .data(),
+        }
+    }
+
+    /// A config with bonding enabled, whose bond token is minted by the payer.
+    struct BondingFixture {
+        config: Pubkey,
+        mint: Pubkey,
+        bond_vault: Pubkey,
+    }
+
+    /// Create the config and a bond token, and require `min_bond` tokens per oracle.
+    async fn setup_bonding(context: &mut ProgramTestContext, min_bond: u64, unbonding_delay_slots: u64) -> BondingFixture {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let program_id = crate::ID;
+        let payer = context.payer.insecure_clone();
+        let config = config_address(program_id);
+        let bond_vault = Pubkey::find_program_address(&[BOND_VAULT_SEED, config.as_ref()], &program_id).0;
+        let mint = Keypair::new();
+        let rent = solana_sdk::rent::Rent::default();
+        let instructions = vec![
+            initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(&spl_token::id(), &mint.pubkey(), &payer.pubkey(), None, 0).unwrap(),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(mint.pubkey(), false),
+                    AccountMeta::new(bond_vault, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::ConfigureBonding { min_bond, unbonding_delay_slots }.data(),
+            },
+        ];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &mint],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        BondingFixture { config, mint: mint.pubkey(), bond_vault }
+    }
+
+    /// Create a token account of `mint` for `owner` holding `amount` tokens minted by the payer.
+    async fn create_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let payer = context.payer.insecure_clone();
+        let token_account = Keypair::new();
+        let rent = solana_sdk::rent::Rent::default();
+        let instructions = vec![
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &token_account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(&spl_token::id(), &token_account.pubkey(), mint, owner).unwrap(),
+            spl_token::instruction::mint_to(&spl_token::id(), mint, &token_account.pubkey(), &payer.pubkey(), &[], amount)
+                .unwrap(),
+        ];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &token_account],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        token_account.pubkey()
+    }
+
+    /// The token balance of `token_account`.
+    async fn token_balance(context: &mut ProgramTestContext, token_account: Pubkey) -> u64 {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let account = context.banks_client.get_account(token_account).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /// Fund `authority` and register `private_key` as its oracle, bonding the minimum from a new
+    /// token account holding `tokens`. Returns the oracle and the token account.
+    async fn register_bonded_oracle(
+        context: &mut ProgramTestContext,
+        bonding: &BondingFixture,
+        authority: &Keypair,
+        private_key: &PrivKey,
+        tokens: u64,
+    ) -> (Pubkey, Pubkey) {
+        let payer = context.payer.insecure_clone();
+        let authority_token_account = create_token_account(context, &bonding.mint, &authority.pubkey(), tokens).await;
+        let (oracle, _) = OracleAccount::find_address(&bonding.config, &authority.pubkey());
+        let mut register = initialize_oracle_instruction(oracle, authority.pubkey(), private_key);
+        register.accounts.extend([
+            AccountMeta::new_readonly(crate::ID, false), // No allowlist entry
+            AccountMeta::new_readonly(crate::ID, false), // No admin
+            AccountMeta::new(bonding.bond_vault, false),
+            AccountMeta::new(authority_token_account, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ]);
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+                register,
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        (oracle, authority_token_account)
+    }
+
+
+#[test]
+fn test_aggregated_verification_off_chain() {
+    // Step 1: Generate private keys and aggregate their G2 points
+    let private_keys: Vec<PrivKey> = (0..5).map(|_| PrivKey::from_random()).collect();
+    let g2_points: Vec<G2Point> = private_keys
+        .iter()
+        .map(|key| G2Point::try_from(key).expect("Failed to derive G2 point from private key"))
+        .collect();
+    let aggregated_compressed_pubkey = BlsUtils::aggregate_and_compress_g2_points(&g2_points).expect("Failed to aggregate and compress G2 points");
+
+    // Step 2: Generate an aggregated G1 signature (off-chain)
+    let message = b"500000.23456".to_vec();
+    let g1_signatures: Vec<G1Point> = private_keys
+        .iter()
+        .map(|key| key.sign::<Sha256Normalized, &[u8]>(&message).expect("Failed to sign message with private key"))
+        .collect();
+    let aggregated_compressed_signature = BlsUtils::aggregate_and_compress_g1_points(&g1_signatures).expect("Failed to aggregate and compress G1 points");
+
+    // Step 3: Verify the signature against the aggregated public key
+    let aggregated_pubkey = G2CompressedPoint(aggregated_compressed_pubkey);
+    let aggregated_signature = G1CompressedPoint(aggregated_compressed_signature);
+    aggregated_pubkey
+        .verify_signature::<Sha256Normalized, &[u8], G1CompressedPoint>(aggregated_signature, &message)
+        .expect("Signature verification failed");
+
+    // Step 4: A different message must not verify against the same signature
+    assert!(aggregated_pubkey
+        .verify_signature::<Sha256Normalized, &[u8], G1CompressedPoint>(aggregated_signature, b"500000.23457")
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_initialize_and_verify_signature() {
+    // Step 1: Set up the Solana test environment
+    let program_id = crate::ID;
+
+    // Step 2: Start the test environment
+    let context = start_program().await;
+    let (mut banks_client, payer, recent_blockhash) =
+        (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+    // Step 3: Derive the oracle PDAs, one per authority
+    let authority = Keypair::new(); // Authority account for Oracle 2
+    let system_program = solana_sdk::system_program::id();
+    let (oracle_account_1, _) = OracleAccount::find_address(&config_address(program_id), &payer.pubkey()); // Oracle 1 account
+    let (oracle_account_2, _) = OracleAccount::find_address(&config_address(program_id), &authority.pubkey()); // Oracle 2 account
+    println!("Payer Pubkey: {}", payer.pubkey());
+    println!("Authority Pubkey: {}", authority.pubkey());
+    println!("Oracle 1 Pubkey: {}", oracle_account_1);
+    println!("Oracle 2 Pubkey: {}", oracle_account_2);
+
+    let private_key_1 = PrivKey::from_random();
+    let private_key_2 = PrivKey::from_random();
+
+    let g2_point_key_2 = G2Point::try_from(&private_key_2).expect("Invalid private key");// Example compressed G2 key for Oracle 1
+    let g2_point_key_1: G2Point = G2Point::try_from(&private_key_1).expect("Invalid private key");// Example compressed G2 key for Oracle 1
+    
+    // Step 4: Initialize the program configuration and Oracle 1
+    let tx = Transaction::new_signed_with_payer(
+        &[initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+         Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(oracle_account_1, false), // Mark oracle as writable, not signing
+                AccountMeta::new(payer.pubkey(), true),        // Authority is a signer
+                AccountMeta::new_readonly(system_program, false),  // System program is read-only
+                AccountMeta::new_readonly(config_address(program_id), false), // Program configuration
+            ],
+            data: instruction::InitializeOracle {
+                g2_point_key: g2_point_key_1.0,
+                pop_signature: proof_of_possession(&private_key_1),
+            }
+            .data(),
+        }],
+        Some(&payer.pubkey()),         // Payer for the transaction
+        &[&payer],                     // Signers: Payer, which is also the authority
+        recent_blockhash,
+    );
+    
+    banks_client.process_transaction(tx).await.unwrap();
+
+    // Step 5: Verify the Oracle 1 was initialized correctly
+    let account_data = banks_client
+        .get_account(oracle_account_1)
+        .await
+        .unwrap()
+        .unwrap();
+    let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+    assert_eq!(oracle_data.authority, payer.pubkey());
+    assert_eq!(oracle_data.g2_point_key, g2_point_key_1.0);
+    println!("✅ Oracle 1 initialized successfully!");
+
+    // Step 6: Fund the second authority and initialize Oracle 2
+    let tx = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+         Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(oracle_account_2, false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new_readonly(config_address(program_id), false),
+            ],
+            data: instruction::InitializeOracle {
+                g2_point_key: g2_point_key_2.0,
+                pop_signature: proof_of_possession(&private_key_2),
+            }
+            .data(),
+        }],
+        Some(&payer.pubkey()),
+        &[&payer, &authority],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(tx).await.unwrap();
+
+    // Verify Oracle 2 was initialized
+    let account_data = banks_client
+        .get_account(oracle_account_2)
+        .await
+        .unwrap()
+        .unwrap();
+    let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+    assert_eq!(oracle_data.authority, authority.pubkey());
+    assert_eq!(oracle_data.g2_point_key, g2_point_key_2.0);
+    println!("✅ Oracle 2 initialized successfully!");
+
+    // Step 7: Verify the aggregated signature
+    let message = b"Test message".to_vec();
+    let g1_signature_1 = private_key_1
+        .sign::<Sha256Normalized, &[u8]>(&application_message(&message))
+        .expect("Failed to sign message with private key 1");
+    let g1_signature_2 = private_key_2
+        .sign::<Sha256Normalized, &[u8]>(&application_message(&message))
+        .expect("Failed to sign message with private key 2");
+    let aggregated_g1_signature = g1_signature_1 + g1_signature_2;
+    let aggregated_compressed_signature: G1CompressedPoint = G1CompressedPoint::try_from(aggregated_g1_signature)
+        .expect("Failed to compress aggregated G1 signature");
+    let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000); // Increase budget
+
+    let tx = Transaction::new_signed_with_payer(
+        &[compute_budget_ix,
+         Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config_address(program_id), false),
+                AccountMeta::new_readonly(oracle_account_1, false),
+                AccountMeta::new_readonly(oracle_account_2, false),
+            ],
+            data: instruction::VerifySignature {
+                aggregated_and_compressed_g1_signature: aggregated_compressed_signature.0,
+                message: message.clone(),
+            }
+            .data(),
+        }],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    let result = banks_client.process_transaction(tx).await;
+
+    // Check result
+    match result {
+        Ok(_) => println!("✅ Signature verified successfully!"),
+        Err(err) => panic!("❌ Signature verification failed: {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oracle_address() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let config = config_address(program_id);
+        let (oracle_account, bump) = OracleAccount::find_address(&config, &payer.pubkey());
+
+        // Step 1: Registration at the authority's PDA succeeds
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+                initialize_oracle_instruction(oracle_account, payer.pubkey(), &PrivKey::from_random()),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let account_data = banks_client.get_account(oracle_account).await.unwrap().unwrap();
+        let oracle = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(oracle.authority, payer.pubkey());
+        assert_eq!(oracle.bump, bump);
+
+        // Step 2: The same authority cannot register a second oracle
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_oracle_instruction(oracle_account, payer.pubkey(), &PrivKey::from_random())],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        // Step 3: A key whose proof of possession was signed by another key is rejected
+        let authority = Keypair::new();
+        let (oracle_account, _) = OracleAccount::find_address(&config, &authority.pubkey());
+        let mut register = initialize_oracle_instruction(oracle_account, authority.pubkey(), &PrivKey::from_random());
+        register.data = instruction::InitializeOracle {
+            g2_point_key: G2Point::try_from(&PrivKey::from_random()).unwrap().0,
+            pop_signature: proof_of_possession(&PrivKey::from_random()),
+        }
+        .data();
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+                register,
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::InvalidProofOfPossession);
+
+        // Step 4: The authority's PDA under another registry is rejected
+        let authority = Keypair::new();
+        let wrong_address = OracleAccount::find_address(&Pubkey::new_unique(), &authority.pubkey()).0;
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+                initialize_oracle_instruction(wrong_address, authority.pubkey(), &PrivKey::from_random()),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_allowlist() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let config = config_address(program_id);
+        let authority = Keypair::new();
+        let allowlist_entry =
+            Pubkey::find_program_address(&[ALLOWLIST_SEED, authority.pubkey().as_ref()], &program_id).0;
+        let (oracle, _) = OracleAccount::find_address(&config, &authority.pubkey());
+        let private_key = PrivKey::from_random();
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Allowlist),
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 1: An authority without an entry cannot register
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_oracle_instruction(oracle, authority.pubkey(), &private_key)],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::NotAllowlisted);
+
+        // Step 2: Only the admin may add an entry
+        let add = |admin: &Keypair| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(allowlist_entry, false),
+                        AccountMeta::new(admin.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::AddToAllowlist { authority: authority.pubkey() }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, admin],
+                recent_blockhash,
+            )
+        };
+        assert_error(banks_client.process_transaction(add(&authority)).await, ErrorCode::UnauthorizedAdmin);
+        banks_client.process_transaction(add(&payer)).await.unwrap();
+
+        // Step 3: With the entry the authority registers
+        let mut register = initialize_oracle_instruction(oracle, authority.pubkey(), &private_key);
+        register.accounts.push(AccountMeta::new_readonly(allowlist_entry, false));
+        let tx = Transaction::new_signed_with_payer(&[register], Some(&payer.pubkey()), &[&payer, &authority], recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+        let account_data = banks_client.get_account(oracle).await.unwrap().unwrap();
+        let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(oracle_data.g2_point_key, G2Point::try_from(&private_key).unwrap().0);
+
+        // Step 4: A signature by a key other than the registered one is rejected
+        let message = b"allowlisted".to_vec();
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                Instruction {
+                    program_id,
+                    accounts: vec![AccountMeta::new_readonly(config, false), AccountMeta::new_readonly(oracle, false)],
+                    data: instruction::VerifySignature {
+                        aggregated_and_compressed_g1_signature: sign_aggregate(&[&PrivKey::from_random()], &application_message(&message)),
+                        message,
+                    }
+                    .data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::SignatureVerificationFailed);
+
+        // Step 5: Only the admin may remove the entry, which closes it
+        let remove = |admin: &Keypair| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(allowlist_entry, false),
+                        AccountMeta::new(admin.pubkey(), true),
+                    ],
+                    data: instruction::RemoveFromAllowlist {}.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, admin],
+                recent_blockhash,
+            )
+        };
+        assert_error(banks_client.process_transaction(remove(&authority)).await, ErrorCode::UnauthorizedAdmin);
+        banks_client.process_transaction(remove(&payer)).await.unwrap();
+        assert!(banks_client.get_account(allowlist_entry).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deregister_oracle() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let system_program = solana_sdk::system_program::id();
+        let (oracle_account, _) = OracleAccount::find_address(&config_address(program_id), &payer.pubkey());
+
+        // Step 1: Register the oracle
+        let private_key = PrivKey::from_random();
+        let g2_point_key = G2Point::try_from(&private_key).expect("Invalid private key");
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(oracle_account, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program, false),
+                    AccountMeta::new_readonly(config_address(program_id), false),
+                ],
+                data: instruction::InitializeOracle {
+                    g2_point_key: g2_point_key.0,
+                    pop_signature: proof_of_possession(&private_key),
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 2: A different signer cannot close the oracle
+        let intruder = Keypair::new();
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(oracle_account, false),
+                    AccountMeta::new(intruder.pubkey(), true),
+                    AccountMeta::new_readonly(config_address(program_id), false),
+                ],
+                data: instruction::DeregisterOracle {}.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer, &intruder],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        // Step 3: The authority deregisters the oracle and the account is closed
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(oracle_account, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(config_address(program_id), false),
+                ],
+                data: instruction::DeregisterOracle {}.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        assert!(banks_client.get_account(oracle_account).await.unwrap().is_none());
+
+        // Step 4: The closed oracle can no longer take part in verification
+        let message = b"Test message".to_vec();
+        let g1_signature = private_key
+            .sign::<Sha256Normalized, &[u8]>(&application_message(&message))
+            .expect("Failed to sign message");
+        let compressed_signature = G1CompressedPoint::try_from(g1_signature)
+            .expect("Failed to compress G1 signature");
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config_address(program_id), false),
+                    AccountMeta::new_readonly(oracle_account, false),
+                ],
+                data: instruction::VerifySignature {
+                    aggregated_and_compressed_g1_signature: compressed_signature.0,
+                    message,
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_admin_transfer_and_pause() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let new_admin = Keypair::new();
+        let config = config_address(program_id);
+        let (oracle_account, _) = OracleAccount::find_address(&config, &payer.pubkey());
+
+        // Step 1: Only the upgrade authority may create the config
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &new_admin.pubkey(), 1_000_000_000),
+                initialize_config_instruction(program_id, new_admin.pubkey(), RegistrationMode::Open),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &new_admin],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::UnauthorizedAdmin);
+
+        // Step 2: Create the config, register an oracle and nominate a new admin
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+                initialize_oracle_instruction(oracle_account, payer.pubkey(), &PrivKey::from_random()),
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new(config, false),
+                        AccountMeta::new_readonly(payer.pubkey(), true),
+                    ],
+                    data: instruction::ProposeAdmin { new_admin: new_admin.pubkey() }.data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 3: The nominee accepts and pauses the program
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new(config, false),
+                        AccountMeta::new_readonly(new_admin.pubkey(), true),
+                    ],
+                    data: instruction::AcceptAdmin {}.data(),
+                },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new(config, false),
+                        AccountMeta::new_readonly(new_admin.pubkey(), true),
+                    ],
+                    data: instruction::SetPaused { paused: true }.data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &new_admin],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let account_data = banks_client.get_account(config).await.unwrap().unwrap();
+        let config_data = ProgramConfig::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(config_data.admin, new_admin.pubkey());
+        assert_eq!(config_data.pending_admin, None);
+        assert!(config_data.paused);
+
+        // Step 4: The previous admin can no longer unpause
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::SetPaused { paused: false }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+
+        // Step 5: Unbonding is rejected while paused
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(oracle_account, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::RequestUnbond { amount: 1 }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::ProgramPaused);
+
+        // Step 6: Registration is rejected while paused
+        let (oracle_account, _) = OracleAccount::find_address(&config, &new_admin.pubkey());
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &new_admin.pubkey(), 1_000_000_000),
+                initialize_oracle_instruction(oracle_account, new_admin.pubkey(), &PrivKey::from_random()),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &new_admin],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::ProgramPaused);
+    }
+
+    #[tokio::test]
+    async fn test_committee_snapshot() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let snapshot_address = |epoch: u64| {
+            Pubkey::find_program_address(&[SNAPSHOT_SEED, committee.as_ref(), &epoch.to_le_bytes()], &program_id).0
+        };
+        let snapshot_instruction = |epoch: u64| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new(snapshot_address(epoch), false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            ],
+            data: instruction::SnapshotCommittee { epoch }.data(),
+        };
+
+        // Step 1: Only the current or previous epoch can be snapshotted
+        let tx = Transaction::new_signed_with_payer(&[snapshot_instruction(5)], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::UnknownEpoch);
+        let tx = Transaction::new_signed_with_payer(&[snapshot_instruction(0)], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+        let account_data = banks_client.get_account(snapshot_address(0)).await.unwrap().unwrap();
+        let snapshot = CommitteeSnapshot::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(snapshot.committee, committee);
+        assert_eq!(snapshot.set.epoch, 0);
+        assert_eq!(snapshot.rent_payer, payer.pubkey());
+
+        // Step 2: Signatures verify against the snapshot, but only over the signed message
+        let message = b"snapshot attestation".to_vec();
+        let signature = sign_aggregate(&[&private_keys[0], &private_keys[1]], &application_message(&message));
+        let verify = |message: Vec<u8>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(snapshot_address(0), false),
+                AccountMeta::new_readonly(committee, false),
+            ],
+            data: instruction::VerifySnapshotSignature {
+                signer_bitmap: 0b011,
+                aggregated_and_compressed_g1_signature: signature,
+                message,
+            }
+            .data(),
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), verify(message.clone())],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), verify(b"other".to_vec())],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::SignatureVerificationFailed);
+
+        // Step 3: The snapshot cannot be closed while the retention policy keeps it
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(committee, false),
+                    AccountMeta::new(snapshot_address(0), false),
+                    AccountMeta::new(payer.pubkey(), false),
+                ],
+                data: instruction::CloseSnapshot {}.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::SnapshotRetained);
+    }
+
+    #[tokio::test]
+    async fn test_committee_rotation() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, members, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+
+        // Step 1: Two of the three members approve epoch 1 with a 3-of-3 threshold
+        let mut accounts = vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(committee, false),
+        ];
+        accounts.extend(members.iter().map(|member| AccountMeta::new(member.oracle, false)));
+        let rotate = |weights: Vec<u64>, recent_blockhash| {
+            let next: Vec<CommitteeMember> = members
+                .iter()
+                .zip(&weights)
+                .map(|(member, weight)| CommitteeMember { weight: *weight, ..member.clone() })
+                .collect();
+            let rotation = rotation_message(&committee, 1, 3, &next);
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: accounts.clone(),
+                        data: instruction::RotateCommittee {
+                            threshold: 3,
+                            weights,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &rotation,
+                            ),
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+
+        // Signed weights cannot exceed the members' stake, which is zero without bonding
+        assert_error(
+            banks_client.process_transaction(rotate(vec![1, 0, 0], recent_blockhash)).await,
+            ErrorCode::WeightAboveStake,
+        );
+        assert_error(
+            banks_client.process_transaction(rotate(vec![0, 0], recent_blockhash)).await,
+            ErrorCode::WeightAboveStake,
+        );
+        banks_client.process_transaction(rotate(vec![0; 3], recent_blockhash)).await.unwrap();
+
+        let account_data = banks_client.get_account(committee).await.unwrap().unwrap();
+        let committee_data = Committee::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(committee_data.current.epoch, 1);
+        assert_eq!(committee_data.previous.as_ref().map(|previous| previous.epoch), Some(0));
+
+        // Step 2: Epoch 1 needs all three signers, epoch 0 still verifies during the handoff window
+        let message = b"Test message".to_vec();
+        let verify = |epoch: u64, signer_bitmap: u32, signature: [u8; 32]| {
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                        ],
+                        data: instruction::VerifyCommitteeSignature {
+                            epoch,
+                            signer_bitmap,
+                            aggregated_and_compressed_g1_signature: signature,
+                            message: message.clone(),
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let two_signers = sign_aggregate(&[&private_keys[0], &private_keys[1]], &application_message(&message));
+        let all_signers =
+            sign_aggregate(&[&private_keys[0], &private_keys[1], &private_keys[2]], &application_message(&message));
+        assert!(banks_client.process_transaction(verify(1, 0b011, two_signers)).await.is_err());
+        banks_client.process_transaction(verify(1, 0b111, all_signers)).await.unwrap();
+        banks_client.process_transaction(verify(0, 0b011, two_signers)).await.unwrap();
+
+        // Step 3: Snapshot epoch 0 and verify the old attestation against it explicitly
+        let snapshot = Pubkey::find_program_address(
+            &[SNAPSHOT_SEED, committee.as_ref(), &0u64.to_le_bytes()],
+            &program_id,
+        )
+        .0;
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new_readonly(committee, false),
+                        AccountMeta::new(snapshot, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::SnapshotCommittee { epoch: 0 }.data(),
+                },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new_readonly(snapshot, false),
+                        AccountMeta::new_readonly(committee, false),
+                    ],
+                    data: instruction::VerifySnapshotSignature {
+                        signer_bitmap: 0b011,
+                        aggregated_and_compressed_g1_signature: two_signers,
+                        message: message.clone(),
+                    }
+                    .data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 4: The snapshot is retained for four epochs, so it cannot be closed yet
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(committee, false),
+                    AccountMeta::new(snapshot, false),
+                    AccountMeta::new(payer.pubkey(), false),
+                ],
+                data: instruction::CloseSnapshot {}.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+    }
+
+    #[test]
+    fn test_merkle_multiproof() {
+        // Step 1: Build a four-member tree over weighted keys
+        let keys: Vec<WeightedKey> = (0..4u8)
+            .map(|i| WeightedKey { g2_point_key: [i; 128], weight: 10 + i as u64 })
+            .collect();
+        let leaves: Vec<[u8; 32]> = keys.iter().map(|key| merkle_leaf(&key.g2_point_key, key.weight)).collect();
+        let root = merkle_parent(&merkle_parent(&leaves[0], &leaves[1]), &merkle_parent(&leaves[2], &leaves[3]));
+        let committee = MerkleCommittee {
+            config: Pubkey::default(),
+            committee_id: 0,
+            root,
+            member_count: 4,
+            total_weight: 46,
+            quorum_weight: 20,
+            bump: 0,
+        };
+
+        // Step 2: Members 0 and 2 sign; their siblings come from the proof
+        let signer_proof = MerkleSignerProof {
+            signers: vec![keys[0].clone(), keys[2].clone()],
+            proof: vec![leaves[1], leaves[3]],
+            proof_flags: vec![false, false, true],
+        };
+        assert_eq!(committee.verify_signer_proof(&signer_proof).unwrap(), 22);
+
+        // Step 3: A member cannot claim more weight than it was committed with
+        let mut inflated = signer_proof.clone();
+        inflated.signers[0].weight = 1_000;
+        assert!(committee.verify_signer_proof(&inflated).is_err());
+
+        // Step 4: A lone member does not reach the quorum
+        let lone = MerkleSignerProof {
+            signers: vec![keys[0].clone()],
+            proof: vec![leaves[1], merkle_parent(&leaves[2], &leaves[3])],
+            proof_flags: vec![false, false],
+        };
+        assert!(committee.verify_signer_proof(&lone).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paged_registry() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let private_keys: Vec<PrivKey> = (0..3).map(|_| PrivKey::from_random()).collect();
+        let PagedRegistryFixture { config, registry, page } =
+            setup_paged_registry(&mut banks_client, &payer, recent_blockhash, &private_keys, 2).await;
+
+        // Step 1: A member whose proof of possession was made by another key is rejected
+        let rogue = PrivKey::from_random();
+        let tx = Transaction::new_signed_with_payer(
+            &[append_member_instruction(config, registry, page, payer.pubkey(), &rogue, proof_of_possession(&private_keys[0]))],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::InvalidProofOfPossession);
+
+        // Step 2: Members 0 and 2 meet the quorum weight of 2
+        let message = b"paged attestation".to_vec();
+        let signature = sign_aggregate(&[&private_keys[0], &private_keys[2]], &application_message(&message));
+        let verify = |signer_indices: Vec<u32>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(registry, false),
+                AccountMeta::new_readonly(page, false),
+            ],
+            data: instruction::VerifyPagedSignature {
+                signer_indices,
+                aggregated_and_compressed_g1_signature: signature,
+                message: message.clone(),
+            }
+            .data(),
+        };
+        let send = |instruction: Instruction| {
+            Transaction::new_signed_with_payer(
+                &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), instruction],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        banks_client.process_transaction(send(verify(vec![0, 2]))).await.unwrap();
+
+        // Step 3: Repeated or unordered indices and a single signer are rejected
+        assert_error(banks_client.process_transaction(send(verify(vec![2, 0]))).await, ErrorCode::InvalidMemberIndex);
+        assert_error(banks_client.process_transaction(send(verify(vec![0, 0]))).await, ErrorCode::InvalidMemberIndex);
+        assert_error(banks_client.process_transaction(send(verify(vec![0]))).await, ErrorCode::ThresholdNotMet);
+
+        // Step 4: Once member 2 is removed its index no longer counts
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(registry, false),
+                    AccountMeta::new(page, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::RemoveMember { slot: 2 }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), verify(vec![0, 2])],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::InvalidMemberIndex);
+        let account_data = banks_client.get_account(registry).await.unwrap().unwrap();
+        let registry_data = PagedRegistry::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(registry_data.member_count, 2);
+        assert_eq!(registry_data.total_weight, 2);
+    }
+
+    #[tokio::test]
+    async fn test_verification_session() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let private_keys: Vec<PrivKey> = (0..3).map(|_| PrivKey::from_random()).collect();
+        let PagedRegistryFixture { config, registry, page } =
+            setup_paged_registry(&mut banks_client, &payer, recent_blockhash, &private_keys, 2).await;
+        let session = Pubkey::find_program_address(
+            &[SESSION_SEED, registry.as_ref(), payer.pubkey().as_ref(), &0u64.to_le_bytes()],
+            &program_id,
+        )
+        .0;
+        let message = b"session attestation".to_vec();
+        let signature = sign_aggregate(&[&private_keys[0], &private_keys[1]], &application_message(&message));
+        let accumulate = |signer_indices: Vec<u32>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(registry, false),
+                AccountMeta::new(session, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new_readonly(page, false),
+            ],
+            data: instruction::AccumulateSessionSigners { signer_indices }.data(),
+        };
+        let finalize = |message: Vec<u8>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(registry, false),
+                AccountMeta::new(session, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+            ],
+            data: instruction::FinalizeVerificationSession { message }.data(),
+        };
+        let send = |instructions: &[Instruction]| {
+            let mut all = vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
+            all.extend_from_slice(instructions);
+            Transaction::new_signed_with_payer(&all, Some(&payer.pubkey()), &[&payer], recent_blockhash)
+        };
+
+        // Step 1: Begin the session and add the first signer
+        let begin = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(registry, false),
+                AccountMeta::new(session, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            ],
+            data: instruction::BeginVerificationSession {
+                _session_id: 0,
+                message_hash: hash(&message).to_bytes(),
+                aggregated_and_compressed_g1_signature: signature,
+            }
+            .data(),
+        };
+        banks_client.process_transaction(send(&[begin, accumulate(vec![0])])).await.unwrap();
+
+        // Step 2: The cursor rejects a signer that was already counted
+        assert_error(banks_client.process_transaction(send(&[accumulate(vec![0])])).await, ErrorCode::InvalidMemberIndex);
+
+        // Step 3: One signer is below the quorum weight; a second one completes it
+        assert_error(banks_client.process_transaction(send(&[finalize(message.clone())])).await, ErrorCode::ThresholdNotMet);
+        banks_client.process_transaction(send(&[accumulate(vec![1])])).await.unwrap();
+
+        // Step 4: The message must match the session's hash, then the session verifies
+        assert_error(banks_client.process_transaction(send(&[finalize(b"other".to_vec())])).await, ErrorCode::MessageMismatch);
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), finalize(message.clone())],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let account_data = banks_client.get_account(session).await.unwrap().unwrap();
+        let session_data = VerificationSession::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert!(session_data.verified);
+        assert_eq!(session_data.signing_weight, 2);
+
+        // Step 5: A verified session cannot be continued
+        assert_error(banks_client.process_transaction(send(&[accumulate(vec![2])])).await, ErrorCode::SessionAlreadyVerified);
+    }
+
+    #[tokio::test]
+    async fn test_update_price() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, members, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+
+        // Step 1: Create a feed bound to the committee
+        let feed_id = [7u8; 32];
+        let (feed, history) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await;
+
+        // Step 2: Two members sign a Borsh-encoded report
+        let update = |report: &PriceReport| {
+            let report = report.try_to_vec().unwrap();
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                            AccountMeta::new_readonly(feed_registry_address(&config), false),
+                            AccountMeta::new(feed, false),
+                            AccountMeta::new(history, false),
+                            AccountMeta::new_readonly(program_id, false),
+                        ],
+                        data: instruction::UpdatePrice {
+                            epoch: 0,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &price_report_message(&report),
+                            ),
+                            report,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let report = PriceReport {
+            feed_id,
+            price: 50_000_023_456,
+            expo: -5,
+            conf: 1_000,
+            publish_time: now,
+            round_id: 1,
+        };
+        banks_client.process_transaction(update(&report)).await.unwrap();
+
+        let account_data = banks_client.get_account(feed).await.unwrap().unwrap();
+        let feed_data = PriceFeed::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(feed_data.latest, report);
+
+        // Step 3: An older report is rejected even with a valid signature
+        let older = PriceReport { publish_time: now - 1, round_id: 2, ..report };
+        assert!(banks_client.process_transaction(update(&older)).await.is_err());
+
+        // Step 4: A report older than the feed's staleness limit, or from far in the future, is rejected
+        let stale = PriceReport { publish_time: now - 7_200, round_id: 3, ..report };
+        assert_error(banks_client.process_transaction(update(&stale)).await, ErrorCode::StalePrice);
+        let future = PriceReport { publish_time: now + 3_600, round_id: 3, ..report };
+        assert_error(banks_client.process_transaction(update(&future)).await, ErrorCode::FutureTimestamp);
+
+        // Step 5: Every member signs its own observation and the feed stores the median, stamped
+        // with the oldest publish time
+        let update_median = |observations: Vec<Vec<u8>>| {
+            let signatures: Vec<G1Point> = private_keys
+                .iter()
+                .zip(&members)
+                .zip(&observations)
+                .map(|((key, member), observation)| {
+                    key.sign::<Sha256Normalized, &[u8]>(&observation_message(&member.oracle, observation)).unwrap()
+                })
+                .collect();
+            let aggregated = signatures.into_iter().reduce(|aggregated, signature| aggregated + signature).unwrap();
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                            AccountMeta::new_readonly(feed_registry_address(&config), false),
+                            AccountMeta::new(feed, false),
+                            AccountMeta::new(history, false),
+                            AccountMeta::new_readonly(program_id, false),
+                        ],
+                        data: instruction::UpdatePriceMedian {
+                            epoch: 0,
+                            signer_bitmap: 0b111,
+                            aggregated_and_compressed_g1_signature: G1CompressedPoint::try_from(aggregated).unwrap().0,
+                            observations,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let observation = |price: i64, conf: u64, publish_time: i64, round_id: u64| {
+            PriceReport { price, conf, publish_time, round_id, ..report }.try_to_vec().unwrap()
+        };
+
+        // Step 5a: A confidence that does not fit the signed median arithmetic is rejected
+        let overflowing = vec![
+            observation(50_000_000_000, u64::MAX, now, 4),
+            observation(50_000_100_000, 1_000, now, 4),
+            observation(61_000_000_000, 1_000, now, 4),
+        ];
+        assert_error(banks_client.process_transaction(update_median(overflowing)).await, ErrorCode::InvalidReport);
+
+        let observations = vec![
+            observation(50_000_000_000, 900, now + 1, 4),
+            observation(50_000_100_000, 1_000, now + 2, 4),
+            observation(61_000_000_000, 5_000, now, 4),
+        ];
+        banks_client.process_transaction(update_median(observations)).await.unwrap();
+
+        let account_data = banks_client.get_account(feed).await.unwrap().unwrap();
+        let feed_data = PriceFeed::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(feed_data.latest.price, 50_000_100_000);
+        assert_eq!(feed_data.latest.conf, 1_000);
+        assert_eq!(feed_data.latest.publish_time, now);
+        assert_eq!((feed_data.min_price, feed_data.max_price), (50_000_000_000, 61_000_000_000));
+        assert_eq!(feed_data.signer_count, 3);
+
+        let account_data = banks_client.get_account(history).await.unwrap().unwrap();
+        let history_data: &PriceHistory = bytemuck::from_bytes(&account_data.data[8..]);
+        assert_eq!(history_data.len, 2);
+        assert_eq!(history_data.iter().last().unwrap().price, 50_000_100_000);
+    }
+
+    #[tokio::test]
+    async fn test_get_twap() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let feed_id = [11u8; 32];
+        let (feed, history) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await;
+
+        // Step 1: The feed is at 100 for 50 seconds, then at 200 for the last 50
+        let now = banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+        for (round_id, price, age) in [(1, 100, 100), (2, 200, 50)] {
+            let report = PriceReport { feed_id, price, expo: -5, publish_time: now - age, round_id, ..PriceReport::default() };
+            let update = update_price_instruction(
+                config,
+                committee,
+                feed,
+                history,
+                &[&private_keys[0], &private_keys[1]],
+                0b011,
+                &report,
+            );
+            let tx = Transaction::new_signed_with_payer(
+                &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), update],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            );
+            banks_client.process_transaction(tx).await.unwrap();
+        }
+
+        // Step 2: The average is returned to the caller as return data
+        let get_twap = |window: i64| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(feed, false),
+                        AccountMeta::new_readonly(feed_registry_address(&config), false),
+                        AccountMeta::new_readonly(history, false),
+                    ],
+                    data: instruction::GetTwap { window }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let result = banks_client.process_transaction_with_metadata(get_twap(100)).await.unwrap();
+        assert!(result.result.is_ok());
+        let return_data = result.metadata.unwrap().return_data.unwrap();
+        assert_eq!(return_data.program_id, program_id);
+        assert_eq!(i64::try_from_slice(&return_data.data).unwrap(), (100 * 50 + 200 * 50) / 100);
+
+        // Step 3: A window reaching past the oldest update is rejected
+        assert_error(banks_client.process_transaction(get_twap(101)).await, ErrorCode::InsufficientHistory);
+    }
+
+    #[tokio::test]
+    async fn test_update_policy() {
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+
+        // Step 1: A feed that only takes a 1% move or a 60 second heartbeat
+        let feed_id = [8u8; 32];
+        let settings = FeedSettings { deviation_bps: 100, heartbeat_secs: 60, ..test_feed_settings() };
+        let (feed, history) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, settings).await;
+        let update = |report: &PriceReport| {
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    update_price_instruction(
+                        config,
+                        committee,
+                        feed,
+                        history,
+                        &[&private_keys[0], &private_keys[1]],
+                        0b011,
+                        report,
+                    ),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        // Step 2: The first report always passes
+        let report = PriceReport {
+            feed_id,
+            price: 100_000_000,
+            expo: -5,
+            conf: 1_000,
+            publish_time: now - 100,
+            round_id: 1,
+        };
+        banks_client.process_transaction(update(&report)).await.unwrap();
+
+        // Step 3: A newer report that barely moved before the heartbeat is rejected
+        let flat = PriceReport { price: 100_500_000, publish_time: now - 90, round_id: 2, ..report };
+        assert_error(banks_client.process_transaction(update(&flat)).await, ErrorCode::UpdateNotNeeded);
+
+        // Step 4: A 1% move is accepted
+        let moved = PriceReport { price: 101_000_000, publish_time: now - 80, round_id: 3, ..report };
+        banks_client.process_transaction(update(&moved)).await.unwrap();
+
+        // Step 5: An unchanged price is accepted once the heartbeat elapsed
+        let heartbeat = PriceReport { publish_time: now - 20, round_id: 4, ..moved };
+        banks_client.process_transaction(update(&heartbeat)).await.unwrap();
+
+        let account_data = banks_client.get_account(feed).await.unwrap().unwrap();
+        let feed_data = PriceFeed::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(feed_data.latest, heartbeat);
+    }
+
+    #[tokio::test]
+    async fn test_claim_rewards() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, members, authorities } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let feed_id = [10u8; 32];
+        let (feed, history) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await;
+        let reward_pool = reward_pool_address(&config);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let update = |round_id: u64, paid: bool, recent_blockhash| {
+            let report = PriceReport { feed_id, price: 100_000, expo: -5, publish_time: now, round_id, ..PriceReport::default() };
+            let mut update = update_price_instruction(
+                config,
+                committee,
+                feed,
+                history,
+                &[&private_keys[0], &private_keys[1]],
+                0b011,
+                &report,
+            );
+            if paid {
+                update.accounts[5] = AccountMeta::new(reward_pool, false);
+            }
+            Transaction::new_signed_with_payer(
+                &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), update],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let claim = |member: usize, recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(members[member].oracle, false),
+                        AccountMeta::new(authorities[member].pubkey(), true),
+                    ],
+                    data: instruction::ClaimRewards {}.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, &authorities[member]],
+                recent_blockhash,
+            )
+        };
+
+        // Step 1: Feeds update without a reward pool
+        banks_client.process_transaction(update(1, false, recent_blockhash)).await.unwrap();
+
+        // Step 2: A funded pool charging 3,000 lamports per update pays each of the two signers 1,500
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::InitializeRewardPool { fee_per_update: 3_000 }.data(),
+                },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::FundRewards { amount: 1_000_000 }.data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        banks_client.process_transaction(update(2, true, recent_blockhash)).await.unwrap();
+
+        let balance = banks_client.get_balance(authorities[0].pubkey()).await.unwrap();
+        banks_client.process_transaction(claim(0, recent_blockhash)).await.unwrap();
+        assert_eq!(banks_client.get_balance(authorities[0].pubkey()).await.unwrap(), balance + 1_500);
+        let account_data = banks_client.get_account(reward_pool).await.unwrap().unwrap();
+        let pool_data = RewardPool::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(pool_data.available, 1_000_000 - 3_000);
+
+        // Step 3: A member that did not sign, or already claimed, has nothing to claim
+        assert_error(banks_client.process_transaction(claim(2, recent_blockhash)).await, ErrorCode::NothingToClaim);
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        assert_error(banks_client.process_transaction(claim(0, recent_blockhash)).await, ErrorCode::NothingToClaim);
+    }
+
+    #[tokio::test]
+    async fn test_feed_registry() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+
+        let feed_id = [9u8; 32];
+        let (feed, history) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await;
+        let feed_registry = feed_registry_address(&config);
+        let update_policy = |admin: &Keypair, settings: FeedSettings| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(feed_registry, false),
+                        AccountMeta::new_readonly(committee, false),
+                        AccountMeta::new_readonly(admin.pubkey(), true),
+                    ],
+                    data: instruction::UpdateFeedPolicy { feed_id, settings }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, admin],
+                recent_blockhash,
+            )
+        };
+        let update = |report: &PriceReport| {
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    update_price_instruction(
+                        config,
+                        committee,
+                        feed,
+                        history,
+                        &[&private_keys[0], &private_keys[1]],
+                        0b011,
+                        report,
+                    ),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let report = PriceReport {
+            feed_id,
+            price: 100_000_000,
+            expo: -5,
+            conf: 1_000,
+            publish_time: now,
+            round_id: 1,
+        };
+
+        // Step 1: Only the admin may change a policy, and only to a valid one
+        let intruder = Keypair::new();
+        let settings = FeedSettings { min_signers: 3, ..test_feed_settings() };
+        assert_error(
+            banks_client.process_transaction(update_policy(&intruder, settings)).await,
+            ErrorCode::UnauthorizedAdmin,
+        );
+        let invalid = FeedSettings { heartbeat_secs: -1, ..test_feed_settings() };
+        assert_error(
+            banks_client.process_transaction(update_policy(&payer, invalid)).await,
+            ErrorCode::InvalidTimestampLimits,
+        );
+
+        // Step 2: Requiring three signers rejects a report two members signed
+        banks_client.process_transaction(update_policy(&payer, settings)).await.unwrap();
+        let account_data = banks_client.get_account(feed_registry).await.unwrap().unwrap();
+        let registry_data = FeedRegistry::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(registry_data.policy(&feed_id, &committee).unwrap().settings, settings);
+        assert_error(banks_client.process_transaction(update(&report)).await, ErrorCode::ThresholdNotMet);
+
+        // Step 3: A retired feed takes no updates and no new policy
+        banks_client.process_transaction(update_policy(&payer, test_feed_settings())).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(feed_registry, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::RetireFeed { feed_id }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let later = PriceReport { round_id: 2, ..report };
+        assert_error(banks_client.process_transaction(update(&later)).await, ErrorCode::FeedRetired);
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(feed_registry, false),
+                    AccountMeta::new_readonly(committee, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::UpdateFeedPolicy { feed_id, settings: test_feed_settings() }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::FeedRetired);
+    }
+
+    #[tokio::test]
+    async fn test_optimistic_report() {
+        let program_id = crate::ID;
+        let mut context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let feed_id = [10u8; 32];
+        let (feed, _) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await;
+
+        // Step 1: Optimistic reports need both a bond and a dispute window
+        let set_parameters = |optimistic_bond: u64, dispute_window_slots: u64| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![AccountMeta::new(config, false), AccountMeta::new_readonly(payer.pubkey(), true)],
+                    data: instruction::SetOptimisticParameters { optimistic_bond, dispute_window_slots }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        assert_error(banks_client.process_transaction(set_parameters(0, 10)).await, ErrorCode::InvalidOptimisticParameters);
+        assert_error(
+            banks_client.process_transaction(set_parameters(1_000_000, 0)).await,
+            ErrorCode::InvalidOptimisticParameters,
+        );
+        banks_client.process_transaction(set_parameters(1_000_000, 10)).await.unwrap();
+
+        // Step 2: A relayer posts a report, bonding lamports
+        let relayer = Keypair::new();
+        let tx = Transaction::new_signed_with_payer(
+            &[solana_sdk::system_instruction::transfer(&payer.pubkey(), &relayer.pubkey(), 1_000_000_000)],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        let optimistic_report_address = |round_id: u64| {
+            Pubkey::find_program_address(
+                &[OPTIMISTIC_SEED, feed.as_ref(), &round_id.to_le_bytes(), relayer.pubkey().as_ref()],
+                &program_id,
+            )
+            .0
+        };
+        let post = |report: PriceReport| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new_readonly(committee, false),
+                        AccountMeta::new_readonly(feed_registry_address(&config), false),
+                        AccountMeta::new_readonly(feed, false),
+                        AccountMeta::new(optimistic_report_address(report.round_id), false),
+                        AccountMeta::new(relayer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::PostOptimisticReport { report }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, &relayer],
+                recent_blockhash,
+            )
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let claimed = PriceReport {
+            feed_id,
+            price: 100_000_000,
+            expo: -5,
+            conf: 1_000,
+            publish_time: now,
+            round_id: 1,
+        };
+        banks_client.process_transaction(post(claimed)).await.unwrap();
+
+        // Step 3: A committee-signed report for the same round that agrees is no dispute; one that
+        // differs takes the bond
+        let challenger = Keypair::new();
+        let challenge = |round_id: u64, report: &PriceReport| {
+            let report = report.try_to_vec().unwrap();
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                            AccountMeta::new_readonly(feed_registry_address(&config), false),
+                            AccountMeta::new_readonly(feed, false),
+                            AccountMeta::new(optimistic_report_address(round_id), false),
+                            AccountMeta::new(challenger.pubkey(), true),
+                        ],
+                        data: instruction::ChallengeOptimisticReport {
+                            epoch: 0,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &price_report_message(&report),
+                            ),
+                            report,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer, &challenger],
+                recent_blockhash,
+            )
+        };
+        assert_error(banks_client.process_transaction(challenge(1, &claimed)).await, ErrorCode::NotEquivocation);
+        let jittered = PriceReport { conf: 2_000, publish_time: now - 1, ..claimed };
+        assert_error(banks_client.process_transaction(challenge(1, &jittered)).await, ErrorCode::NotEquivocation);
+        let attested = PriceReport { price: 90_000_000, ..claimed };
+        banks_client.process_transaction(challenge(1, &attested)).await.unwrap();
+        assert!(banks_client.get_account(optimistic_report_address(1)).await.unwrap().is_none());
+        let challenger_data = banks_client.get_account(challenger.pubkey()).await.unwrap().unwrap();
+        assert!(challenger_data.lamports > 1_000_000);
+
+        // Step 4: An unchallenged report cannot be disputed or finalized early...
+        let later = PriceReport { round_id: 2, ..claimed };
+        banks_client.process_transaction(post(later)).await.unwrap();
+        let finalize = |round_id: u64, recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new_readonly(feed_registry_address(&config), false),
+                        AccountMeta::new(feed, false),
+                        AccountMeta::new(optimistic_report_address(round_id), false),
+                        AccountMeta::new(relayer.pubkey(), false),
+                    ],
+                    data: instruction::FinalizeOptimisticReport {}.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        assert_error(banks_client.process_transaction(finalize(2, recent_blockhash)).await, ErrorCode::DisputeWindowOpen);
+
+        // ...and after the window closes it can no longer be disputed, only finalized
+        context.warp_to_slot(100).unwrap();
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let mut dispute = challenge(2, &PriceReport { price: 90_000_000, ..later });
+        dispute.sign(&[&payer, &challenger], recent_blockhash);
+        assert_error(banks_client.process_transaction(dispute).await, ErrorCode::DisputeWindowClosed);
+        banks_client.process_transaction(finalize(2, recent_blockhash)).await.unwrap();
+        let account_data = banks_client.get_account(feed).await.unwrap().unwrap();
+        let feed_data = PriceFeed::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(feed_data.optimistic, later);
+        assert!(banks_client.get_account(optimistic_report_address(2)).await.unwrap().is_none());
+
+        // Step 5: Retiring a feed does not lock bonds: its reports are refunded but not stored
+        let last = PriceReport { round_id: 3, ..later };
+        let mut tx = post(last);
+        tx.sign(&[&payer, &relayer], recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(feed_registry_address(&config), false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::RetireFeed { feed_id }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+        context.warp_to_slot(200).unwrap();
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let relayer_lamports = banks_client.get_balance(relayer.pubkey()).await.unwrap();
+        banks_client.process_transaction(finalize(3, recent_blockhash)).await.unwrap();
+        assert!(banks_client.get_balance(relayer.pubkey()).await.unwrap() > relayer_lamports + 1_000_000);
+        let account_data = banks_client.get_account(feed).await.unwrap().unwrap();
+        let feed_data = PriceFeed::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(feed_data.optimistic, later);
+    }
+
+    #[test]
+    fn test_twap() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        let report = |price, publish_time| PriceReport { price, publish_time, ..PriceReport::default() };
+        assert!(history.twap(1_000, 100).is_err()); // Empty
+
+        history.push(&report(100, 900), 1);
+        history.push(&report(200, 950), 2);
+        history.push(&report(400, 990), 3);
+        // 100 for [900, 950), 200 for [950, 990), 400 for [990, 1000)
+        assert_eq!(history.twap(1_000, 100).unwrap(), (100 * 50 + 200 * 40 + 400 * 10) / 100);
+        assert_eq!(history.twap(1_000, 20).unwrap(), (200 * 10 + 400 * 10) / 20);
+        assert!(history.twap(1_000, 101).is_err()); // Reaches past the oldest entry
+
+        // Wrapping around keeps the newest entries in order
+        for i in 0..HISTORY_CAPACITY as i64 {
+            history.push(&report(i, 1_000 + i), 4);
+        }
+        assert_eq!(history.len as usize, HISTORY_CAPACITY);
+        assert_eq!(history.iter().next().unwrap().price, 0);
+        assert_eq!(history.iter().last().unwrap().price, HISTORY_CAPACITY as i64 - 1);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [5]), 5);
+        assert_eq!(median(&mut [9, 1, 5]), 5);
+        assert_eq!(median(&mut [4, 1, 3, 2]), 2);
+        assert_eq!(median(&mut [i64::MAX, i64::MAX]), i64::MAX);
+    }
+
+    #[test]
+    fn test_staleness_checks() {
+        assert!(check_timestamp(1_000, 1_000, 60, 5).is_ok());
+        assert!(check_timestamp(940, 1_000, 60, 5).is_ok());
+        assert!(check_timestamp(939, 1_000, 60, 5).is_err());
+        assert!(check_timestamp(1_006, 1_000, 60, 5).is_err());
+        assert!(check_timestamp(0, 1_000, 0, 0).is_ok()); // Limits disabled
+
+        let mut feed = PriceFeed {
+            config: Pubkey::default(),
+            feed_id: [0; 32],
+            latest: PriceReport::default(),
+            last_update_slot: 0,
+            min_price: 0,
+            max_price: 0,
+            signer_count: 0,
+            optimistic: PriceReport::default(),
+            optimistic_update_slot: 0,
+            bump: 0,
+        };
+        assert!(feed.get_price_no_older_than(1_000, 60).is_err()); // Never updated
+        feed.latest = PriceReport { price: 42, publish_time: 950, round_id: 1, ..PriceReport::default() };
+        assert_eq!(feed.get_price_no_older_than(1_000, 60).unwrap().price, 42);
+        assert!(feed.get_price_no_older_than(1_000, 10).is_err());
+
+        // 1% deviation or a 60 second heartbeat, signed by at least two members
+        let mut policy = FeedPolicy {
+            feed_id: [0; 32],
+            committee: Pubkey::default(),
+            settings: FeedSettings { min_signers: 2, deviation_bps: 100, heartbeat_secs: 60, ..FeedSettings::default() },
+            retired: false,
+            frozen: false,
+            frozen_slot: 0,
+        };
+        let next = |price, publish_time| PriceReport { price, publish_time, round_id: 2, ..PriceReport::default() };
+        assert!(policy.check_update(&feed.latest, &next(42, 1_009), 2).is_err());
+        assert!(policy.check_update(&feed.latest, &next(43, 1_009), 2).is_ok()); // Moved 2.4%
+        assert!(policy.check_update(&feed.latest, &next(42, 1_010), 2).is_ok()); // Heartbeat elapsed
+        assert!(policy.check_update(&feed.latest, &next(43, 1_009), 1).is_err()); // Too few signers
+        policy.settings.heartbeat_secs = 0;
+        assert!(policy.check_update(&feed.latest, &next(42, 5_000), 2).is_err()); // Deviation only
+
+        // Optimistic reads prefer a newer finalized optimistic report
+        let clock = Clock { unix_timestamp: 960, ..Clock::default() };
+        assert_eq!(feed.get_optimistic_price(&policy, &clock).unwrap().price, 42);
+        feed.optimistic = PriceReport { price: 44, publish_time: 955, round_id: 2, ..PriceReport::default() };
+        assert_eq!(feed.get_optimistic_price(&policy, &clock).unwrap().price, 44);
+        assert_eq!(feed.get_price(&policy, &clock).unwrap().price, 42);
+
+        // Frozen and retired feeds can no longer be read
+        policy.frozen = true;
+        assert!(feed.get_price(&policy, &clock).is_err());
+        assert!(feed.get_optimistic_price(&policy, &clock).is_err());
+        policy.frozen = false;
+        assert!(feed.get_price(&policy, &Clock { unix_timestamp: 960, ..Clock::default() }).is_ok());
+        policy.retired = true;
+        assert!(feed.get_price(&policy, &Clock { unix_timestamp: 960, ..Clock::default() }).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_price_from_root() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+
+        // Step 1: Two feeds and a report for each, hashed into a two-leaf tree
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let mut feeds = Vec::new();
+        let mut reports = Vec::new();
+        for (feed_id, price) in [([1u8; 32], 100i64), ([2u8; 32], 200)] {
+            feeds.push(create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await);
+            reports.push(
+                PriceReport { feed_id, price, expo: -5, conf: 1, publish_time: now, round_id: 1 }.try_to_vec().unwrap(),
+            );
+        }
+        let leaves: Vec<[u8; 32]> = reports.iter().map(|report| price_leaf(report)).collect();
+        let root = merkle_parent(&leaves[0], &leaves[1]);
+
+        // Step 2: The committee signs the root once
+        let attested_root =
+            Pubkey::find_program_address(&[ATTESTED_ROOT_SEED, committee.as_ref(), &root], &program_id).0;
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new_readonly(committee, false),
+                        AccountMeta::new(attested_root, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                        AccountMeta::new_readonly(program_id, false),
+                    ],
+                    data: instruction::AttestPriceRoot {
+                        epoch: 0,
+                        signer_bitmap: 0b110,
+                        aggregated_and_compressed_g1_signature: sign_aggregate(
+                            &[&private_keys[1], &private_keys[2]],
+                            &price_root_message(&committee, &root),
+                        ),
+                        root,
+                    }
+                    .data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 3: Each feed is updated from its leaf and proof
+        let update = |index: usize, report: Vec<u8>, proof: Vec<[u8; 32]>| {
+            let (feed, history) = feeds[index];
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new_readonly(committee, false),
+                        AccountMeta::new_readonly(attested_root, false),
+                        AccountMeta::new_readonly(feed_registry_address(&config), false),
+                        AccountMeta::new(feed, false),
+                        AccountMeta::new(history, false),
+                    ],
+                    data: instruction::UpdatePriceFromRoot { report, proof }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        // A leaf proven against the wrong feed is rejected
+        assert!(banks_client
+            .process_transaction(update(0, reports[1].clone(), vec![leaves[0]]))
+            .await
+            .is_err());
+        for index in 0..2 {
+            let tx = update(index, reports[index].clone(), vec![leaves[1 - index]]);
+            banks_client.process_transaction(tx).await.unwrap();
+        }
+
+        let account_data = banks_client.get_account(feeds[1].0).await.unwrap().unwrap();
+        let feed_data = PriceFeed::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(feed_data.latest.price, 200);
+        assert_eq!(feed_data.signer_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_frozen_committee() {
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, members, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let keys = [&private_keys[0], &private_keys[1]];
+        let send = |instruction: Instruction, recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), instruction],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+
+        // Step 1: A feed with one update, a root attested over a later report and a snapshot
+        let feed_id = [1u8; 32];
+        let (feed, history) =
+            create_price_feed(&mut banks_client, &payer, recent_blockhash, config, committee, feed_id, test_feed_settings())
+                .await;
+        let report = PriceReport { feed_id, price: 100, expo: -5, conf: 1, publish_time: now - 30, round_id: 1 };
+        let tx = send(update_price_instruction(config, committee, feed, history, &keys, 0b011, &report), recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let leaf = PriceReport { price: 101, publish_time: now, round_id: 2, ..report }.try_to_vec().unwrap();
+        let root = price_leaf(&leaf);
+        let attested_root =
+            Pubkey::find_program_address(&[ATTESTED_ROOT_SEED, committee.as_ref(), &root], &program_id).0;
+        let attest = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new(attested_root, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                AccountMeta::new_readonly(program_id, false),
+            ],
+            data: instruction::AttestPriceRoot {
+                epoch: 0,
+                signer_bitmap: 0b011,
+                aggregated_and_compressed_g1_signature: sign_aggregate(&keys, &price_root_message(&committee, &root)),
+                root,
+            }
+            .data(),
+        };
+        banks_client.process_transaction(send(attest, recent_blockhash)).await.unwrap();
+
+        let snapshot =
+            Pubkey::find_program_address(&[SNAPSHOT_SEED, committee.as_ref(), &0u64.to_le_bytes()], &program_id).0;
+        let take_snapshot = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new(snapshot, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            ],
+            data: instruction::SnapshotCommittee { epoch: 0 }.data(),
+        };
+        banks_client.process_transaction(send(take_snapshot, recent_blockhash)).await.unwrap();
+
+        // Step 2: Two conflicting reports for round 3 freeze the committee and its feed
+        let signed = |price: i64| {
+            let report = PriceReport { price, round_id: 3, publish_time: now, ..report }.try_to_vec().unwrap();
+            CommitteeSignedReport {
+                epoch: 0,
+                signer_bitmap: 0b011,
+                aggregated_and_compressed_g1_signature: sign_aggregate(&keys, &price_report_message(&report)),
+                report,
+            }
+        };
+        let equivocation = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(committee, false),
+                AccountMeta::new(feed_registry_address(&config), false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+            ],
+            data: instruction::ReportCommitteeEquivocation {
+                report_a: signed(102),
+                report_b: signed(103),
+                freeze_committee: true,
+            }
+            .data(),
+        };
+        banks_client.process_transaction(send(equivocation, recent_blockhash)).await.unwrap();
+        let account_data = banks_client.get_account(committee).await.unwrap().unwrap();
+        let committee_data = Committee::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert!(committee_data.frozen);
+        assert!(committee_data.frozen_slot >= 1);
+
+        // Step 3: Direct updates, roots, snapshots and rotations all need an unfrozen committee
+        let next = PriceReport { round_id: 4, ..report };
+        let tx = send(update_price_instruction(config, committee, feed, history, &keys, 0b011, &next), recent_blockhash);
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::CommitteeFrozen);
+
+        let update_from_root = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new_readonly(attested_root, false),
+                AccountMeta::new_readonly(feed_registry_address(&config), false),
+                AccountMeta::new(feed, false),
+                AccountMeta::new(history, false),
+            ],
+            data: instruction::UpdatePriceFromRoot { report: leaf.clone(), proof: Vec::new() }.data(),
+        };
+        assert_error(
+            banks_client.process_transaction(send(update_from_root.clone(), recent_blockhash)).await,
+            ErrorCode::CommitteeFrozen,
+        );
+
+        let message = b"snapshot attestation".to_vec();
+        let verify_snapshot = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(snapshot, false),
+                AccountMeta::new_readonly(committee, false),
+            ],
+            data: instruction::VerifySnapshotSignature {
+                signer_bitmap: 0b011,
+                aggregated_and_compressed_g1_signature: sign_aggregate(&keys, &application_message(&message)),
+                message,
+            }
+            .data(),
+        };
+        assert_error(
+            banks_client.process_transaction(send(verify_snapshot, recent_blockhash)).await,
+            ErrorCode::CommitteeFrozen,
+        );
+
+        let mut accounts = vec![AccountMeta::new_readonly(config, false), AccountMeta::new(committee, false)];
+        accounts.extend(members.iter().map(|member| AccountMeta::new(member.oracle, false)));
+        let rotate = Instruction {
+            program_id,
+            accounts,
+            data: instruction::RotateCommittee {
+                threshold: 2,
+                weights: vec![0; 3],
+                signer_bitmap: 0b011,
+                aggregated_and_compressed_g1_signature: sign_aggregate(&keys, &rotation_message(&committee, 1, 2, &members)),
+            }
+            .data(),
+        };
+        assert_error(banks_client.process_transaction(send(rotate, recent_blockhash)).await, ErrorCode::CommitteeFrozen);
+
+        // Step 4: The frozen feed cannot be read
+        let twap = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(feed, false),
+                AccountMeta::new_readonly(feed_registry_address(&config), false),
+                AccountMeta::new_readonly(history, false),
+            ],
+            data: instruction::GetTwap { window: 10 }.data(),
+        };
+        assert_error(banks_client.process_transaction(send(twap.clone(), recent_blockhash)).await, ErrorCode::FeedFrozen);
+
+        // Step 5: After review both are unfrozen, but the root attested before the freeze stays unusable
+        let unfreeze = vec![
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(committee, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::UnfreezeCommittee {}.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(feed_registry_address(&config), false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::UnfreezeFeed { feed_id }.data(),
+            },
+        ];
+        let tx = Transaction::new_signed_with_payer(&unfreeze, Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        assert_error(
+            banks_client.process_transaction(send(update_from_root, recent_blockhash)).await,
+            ErrorCode::AttestedBeforeFreeze,
+        );
+        banks_client.process_transaction(send(twap, recent_blockhash)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_governance() {
+        let program_id = crate::ID;
+        let mut context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let execute = |proposal: GovernanceProposal, recent_blockhash| {
+            let message = governance_message(&config, &committee, &proposal).unwrap();
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new(config, false),
+                            AccountMeta::new(committee, false),
+                            AccountMeta::new_readonly(program_id, false),
+                            AccountMeta::new_readonly(program_id, false),
+                        ],
+                        data: instruction::ExecuteGovernance {
+                            epoch: 0,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &message,
+                            ),
+                            proposal,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+        let read_config = |account: solana_sdk::account::Account| {
+            ProgramConfig::try_deserialize(&mut &account.data[..]).unwrap()
+        };
+        let pause = GovernanceProposal { nonce: 0, expiry_slot: 1_000, action: GovernanceAction::SetPaused { paused: true } };
+
+        // Step 1: Only the governance committee can sign proposals, and there is none yet
+        assert_error(banks_client.process_transaction(execute(pause, recent_blockhash)).await, ErrorCode::CommitteeMismatch);
+
+        let set_governance_committee = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+            ],
+            data: instruction::SetGovernanceCommittee {}.data(),
+        };
+        let tx = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&set_governance_committee),
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 2: From then on the admin can neither pause nor pick another governance committee
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let set_paused = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(config, false), AccountMeta::new_readonly(payer.pubkey(), true)],
+            data: instruction::SetPaused { paused: true }.data(),
+        };
+        for instruction in [set_paused, set_governance_committee] {
+            let tx = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+            assert_error(banks_client.process_transaction(tx).await, ErrorCode::GovernedByCommittee);
+        }
+
+        // Step 3: A signed proposal executes once and consumes the nonce
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        banks_client.process_transaction(execute(pause, recent_blockhash)).await.unwrap();
+        let config_data = read_config(banks_client.get_account(config).await.unwrap().unwrap());
+        assert!(config_data.paused);
+        assert_eq!(config_data.governance_nonce, 1);
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        assert_error(banks_client.process_transaction(execute(pause, recent_blockhash)).await, ErrorCode::InvalidNonce);
+
+        // Step 4: A proposal past its expiry slot is rejected even with the right nonce
+        context.warp_to_slot(100).unwrap();
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let unpause = GovernanceProposal { nonce: 1, expiry_slot: 50, action: GovernanceAction::SetPaused { paused: false } };
+        assert_error(banks_client.process_transaction(execute(unpause, recent_blockhash)).await, ErrorCode::ProposalExpired);
+
+        let unpause = GovernanceProposal { expiry_slot: 1_000, ..unpause };
+        banks_client.process_transaction(execute(unpause, recent_blockhash)).await.unwrap();
+        let config_data = read_config(banks_client.get_account(config).await.unwrap().unwrap());
+        assert!(!config_data.paused);
+        assert_eq!(config_data.governance_nonce, 2);
+    }
+
+    #[tokio::test]
+    async fn test_admin_changes_governed() {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let merkle_committee =
+            Pubkey::find_program_address(&[MERKLE_COMMITTEE_SEED, config.as_ref(), &0u64.to_le_bytes()], &program_id).0;
+        let registry = Pubkey::find_program_address(&[REGISTRY_SEED, config.as_ref(), &0u64.to_le_bytes()], &program_id).0;
+        let page = Pubkey::find_program_address(&[REGISTRY_PAGE_SEED, registry.as_ref(), &0u32.to_le_bytes()], &program_id).0;
+        let allowlisted = Pubkey::find_program_address(&[ALLOWLIST_SEED, payer.pubkey().as_ref()], &program_id).0;
+        let bond_vault = Pubkey::find_program_address(&[BOND_VAULT_SEED, config.as_ref()], &program_id).0;
+        let mint = Keypair::new();
+        let treasury = Keypair::new();
+        let rent = solana_sdk::rent::Rent::default();
+        let system_program = solana_sdk::system_program::id();
+
+        // Step 1: Bonding, a treasury, a Merkle committee, a paged registry, an allowlist entry and a reward pool
+        let instructions = vec![
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(&spl_token::id(), &mint.pubkey(), &payer.pubkey(), None, 0).unwrap(),
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &treasury.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(&spl_token::id(), &treasury.pubkey(), &mint.pubkey(), &payer.pubkey())
+                .unwrap(),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(mint.pubkey(), false),
+                    AccountMeta::new(bond_vault, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::ConfigureBonding { min_bond: 1, unbonding_delay_slots: 10 }.data(),
+            },
+        ];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &mint, &treasury],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let instructions = vec![
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(merkle_committee, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::InitializeMerkleCommittee {
+                    committee_id: 0,
+                    root: [1; 32],
+                    member_count: 1,
+                    total_weight: 1,
+                    quorum_weight: 1,
+                }
+                .data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(registry, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::InitializePagedRegistry { registry_id: 0, quorum_weight: 1 }.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(registry, false),
+                    AccountMeta::new(page, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::AddRegistryPage {}.data(),
+            },
+            append_member_instruction(
+                config,
+                registry,
+                page,
+                payer.pubkey(),
+                &private_keys[0],
+                proof_of_possession(&private_keys[0]),
+            ),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(allowlisted, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::AddToAllowlist { authority: payer.pubkey() }.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(reward_pool_address(&config), false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::InitializeRewardPool { fee_per_update: 0 }.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(committee, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::SetGovernanceCommittee {}.data(),
+            },
+        ];
+        let tx = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 2: Once governance is set, every admin change is rejected
+        let admin = AccountMeta::new(payer.pubkey(), true);
+        let config_change = |data: Vec<u8>| Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(config, false), admin.clone()],
+            data,
+        };
+        let committee_change = |data: Vec<u8>| Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(config, false), AccountMeta::new(committee, false), admin.clone()],
+            data,
+        };
+        let page_change = |data: Vec<u8>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(registry, false),
+                AccountMeta::new(page, false),
+                admin.clone(),
+            ],
+            data,
+        };
+        let changes = vec![
+            config_change(
+                instruction::UpdateConfig { registration_mode: RegistrationMode::AdminOnly, max_committee_size: 4 }.data(),
+            ),
+            config_change(instruction::ProposeAdmin { new_admin: Pubkey::new_unique() }.data()),
+            config_change(instruction::SetBondParameters { min_bond: 2, unbonding_delay_slots: 20 }.data()),
+            config_change(instruction::SetOptimisticParameters { optimistic_bond: 1, dispute_window_slots: 1 }.data()),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(treasury.pubkey(), false),
+                    admin.clone(),
+                ],
+                data: instruction::SetSlashingParameters { slash_bps: 5_000, reporter_reward_bps: 2_000 }.data(),
+            },
+            committee_change(instruction::SetHandoffWindow { handoff_window_slots: 10 }.data()),
+            committee_change(instruction::SetTimestampLimits { max_staleness: 10, max_future_skew: 10 }.data()),
+            committee_change(instruction::SetSnapshotRetention { snapshot_retention_epochs: 10 }.data()),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(merkle_committee, false),
+                    admin.clone(),
+                ],
+                data: instruction::UpdateMerkleCommittee {
+                    root: [2; 32],
+                    member_count: 1,
+                    total_weight: 1,
+                    quorum_weight: 1,
+                }
+                .data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![AccountMeta::new_readonly(config, false), AccountMeta::new(registry, false), admin.clone()],
+                data: instruction::SetRegistryQuorum { quorum_weight: 2 }.data(),
+            },
+            append_member_instruction(
+                config,
+                registry,
+                page,
+                payer.pubkey(),
+                &private_keys[1],
+                proof_of_possession(&private_keys[1]),
+            ),
+            page_change(instruction::RemoveMember { slot: 0 }.data()),
+            page_change(instruction::CompactPage {}.data()),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(Pubkey::find_program_address(&[ALLOWLIST_SEED, committee.as_ref()], &program_id).0, false),
+                    admin.clone(),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+                data: instruction::AddToAllowlist { authority: committee }.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![AccountMeta::new_readonly(config, false), AccountMeta::new(allowlisted, false), admin.clone()],
+                data: instruction::RemoveFromAllowlist {}.data(),
+            },
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(reward_pool_address(&config), false),
+                    admin.clone(),
+                ],
+                data: instruction::SetUpdateFee { fee_per_update: 1 }.data(),
+            },
+        ];
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        for change in changes {
+            let tx = Transaction::new_signed_with_payer(&[change], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+            assert_error(banks_client.process_transaction(tx).await, ErrorCode::GovernedByCommittee);
+        }
+
+        // Step 3: The governance committee makes the config and committee changes instead
+        let actions = [
+            GovernanceAction::UpdateConfig { registration_mode: RegistrationMode::AdminOnly, max_committee_size: 4 },
+            GovernanceAction::SetBondParameters { min_bond: 2, unbonding_delay_slots: 20 },
+            GovernanceAction::SetHandoffWindow { committee, handoff_window_slots: 10 },
+            GovernanceAction::SetTimestampLimits { committee, max_staleness: 10, max_future_skew: 10 },
+        ];
+        for (nonce, action) in actions.into_iter().enumerate() {
+            let proposal = GovernanceProposal { nonce: nonce as u64, expiry_slot: 1_000, action };
+            let message = governance_message(&config, &committee, &proposal).unwrap();
+            let tx = Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new(config, false),
+                            AccountMeta::new(committee, false),
+                            AccountMeta::new_readonly(program_id, false),
+                            AccountMeta::new_readonly(program_id, false),
+                        ],
+                        data: instruction::ExecuteGovernance {
+                            epoch: 0,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &message,
+                            ),
+                            proposal,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            );
+            banks_client.process_transaction(tx).await.unwrap();
+        }
+        let account = banks_client.get_account(config).await.unwrap().unwrap();
+        let config_data = ProgramConfig::try_deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(config_data.registration_mode, RegistrationMode::AdminOnly);
+        assert_eq!((config_data.max_committee_size, config_data.min_bond, config_data.unbonding_delay_slots), (4, 2, 20));
+        let account = banks_client.get_account(committee).await.unwrap().unwrap();
+        let committee_data = Committee::try_deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(
+            (committee_data.handoff_window_slots, committee_data.max_staleness, committee_data.max_future_skew),
+            (10, 10, 10),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vault_transfer() {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let program_id = crate::ID;
+        let mut context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let vault = Pubkey::find_program_address(&[VAULT_SEED, committee.as_ref()], &program_id).0;
+        let token_balance = |data: &[u8]| spl_token::state::Account::unpack(data).unwrap().amount;
+
+        // Step 1: A vault holding SOL and 1,000 tokens
+        let mint = Keypair::new();
+        let vault_token_account = Keypair::new();
+        let destination_token_account = Keypair::new();
+        let rent = solana_sdk::rent::Rent::default();
+        let mut instructions = vec![
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new_readonly(committee, false),
+                    AccountMeta::new(vault, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::InitializeVault {}.data(),
+            },
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &vault, 1_000_000_000),
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(&spl_token::id(), &mint.pubkey(), &payer.pubkey(), None, 0).unwrap(),
+        ];
+        for (account, owner) in [(&vault_token_account, vault), (&destination_token_account, payer.pubkey())] {
+            instructions.push(solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ));
+            instructions.push(
+                spl_token::instruction::initialize_account(&spl_token::id(), &account.pubkey(), &mint.pubkey(), &owner)
+                    .unwrap(),
+            );
+        }
+        instructions.push(
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &vault_token_account.pubkey(),
+                &payer.pubkey(),
+                &[],
+                1_000,
+            )
+            .unwrap(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &mint, &vault_token_account, &destination_token_account],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let execute = |intent: TransferIntent, recent_blockhash| {
+            let message = vault_transfer_message(&vault, &intent).unwrap();
+            let (source, token_program) = match intent.mint {
+                Some(_) => (vault_token_account.pubkey(), spl_token::id()),
+                None => (program_id, program_id),
+            };
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                            AccountMeta::new(vault, false),
+                            AccountMeta::new(intent.destination, false),
+                            AccountMeta::new(source, false),
+                            AccountMeta::new_readonly(token_program, false),
+                        ],
+                        data: instruction::ExecuteVaultTransfer {
+                            epoch: 0,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &message,
+                            ),
+                            intent,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+
+        // Step 2: A SOL transfer pays the destination and consumes the nonce
+        let recipient = Pubkey::new_unique();
+        let sol_transfer =
+            TransferIntent { destination: recipient, mint: None, amount: 10_000_000, nonce: 0, expiry_slot: 1_000 };
+        banks_client.process_transaction(execute(sol_transfer, recent_blockhash)).await.unwrap();
+        assert_eq!(banks_client.get_balance(recipient).await.unwrap(), 10_000_000);
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        assert_error(banks_client.process_transaction(execute(sol_transfer, recent_blockhash)).await, ErrorCode::InvalidNonce);
+
+        // Step 3: Empty transfers are rejected, token transfers move tokens out of the vault's account
+        let empty = TransferIntent { amount: 0, nonce: 1, ..sol_transfer };
+        assert_error(banks_client.process_transaction(execute(empty, recent_blockhash)).await, ErrorCode::InvalidAmount);
+
+        let token_transfer = TransferIntent {
+            destination: destination_token_account.pubkey(),
+            mint: Some(mint.pubkey()),
+            amount: 400,
+            nonce: 1,
+            expiry_slot: 1_000,
+        };
+        banks_client.process_transaction(execute(token_transfer, recent_blockhash)).await.unwrap();
+        let account = banks_client.get_account(destination_token_account.pubkey()).await.unwrap().unwrap();
+        assert_eq!(token_balance(&account.data), 400);
+        let account = banks_client.get_account(vault_token_account.pubkey()).await.unwrap().unwrap();
+        assert_eq!(token_balance(&account.data), 600);
+
+        // Step 4: An intent past its expiry slot is rejected even with the right nonce
+        context.warp_to_slot(100).unwrap();
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let expired = TransferIntent { nonce: 2, expiry_slot: 50, ..sol_transfer };
+        assert_error(banks_client.process_transaction(execute(expired, recent_blockhash)).await, ErrorCode::ProposalExpired);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_claim() {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let program_id = crate::ID;
+        let context = start_program().await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+        let CommitteeFixture { config, committee, private_keys, .. } =
+            setup_committee(&mut banks_client, &payer, recent_blockhash, 3, 2).await;
+        let bridge =
+            Pubkey::find_program_address(&[BRIDGE_SEED, committee.as_ref(), &1u64.to_le_bytes()], &program_id).0;
+        let mint = Pubkey::find_program_address(&[WRAPPED_MINT_SEED, bridge.as_ref()], &program_id).0;
+        let token_balance = |data: &[u8]| spl_token::state::Account::unpack(data).unwrap().amount;
+
+        // Step 1: A bridge from chain 1, and wrapped token accounts for the recipient and someone else
+        let recipient = Pubkey::new_unique();
+        let recipient_token_account = Keypair::new();
+        let other_token_account = Keypair::new();
+        let rent = solana_sdk::rent::Rent::default();
+        let mut instructions = vec![Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(committee, false),
+                AccountMeta::new(bridge, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            ],
+            data: instruction::InitializeBridge { source_chain_id: 1, decimals: 6 }.data(),
+        }];
+        for (account, owner) in [(&recipient_token_account, recipient), (&other_token_account, Pubkey::new_unique())] {
+            instructions.push(solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ));
+            instructions.push(
+                spl_token::instruction::initialize_account(&spl_token::id(), &account.pubkey(), &mint, &owner).unwrap(),
+            );
+        }
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &recipient_token_account, &other_token_account],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let consumed_deposit = |deposit: &DepositAttestation| {
+            Pubkey::find_program_address(
+                &[DEPOSIT_SEED, bridge.as_ref(), &deposit.tx_hash, &deposit.nonce.to_le_bytes()],
+                &program_id,
+            )
+            .0
+        };
+        let claim = |deposit: DepositAttestation, token_account: Pubkey, recent_blockhash| {
+            let message = bridge_deposit_message(&bridge, &deposit).unwrap();
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                            AccountMeta::new_readonly(bridge, false),
+                            AccountMeta::new(mint, false),
+                            AccountMeta::new(consumed_deposit(&deposit), false),
+                            AccountMeta::new(token_account, false),
+                            AccountMeta::new(payer.pubkey(), true),
+                            AccountMeta::new_readonly(spl_token::id(), false),
+                            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                        ],
+                        data: instruction::Claim {
+                            epoch: 0,
+                            signer_bitmap: 0b011,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_keys[0], &private_keys[1]],
+                                &message,
+                            ),
+                            deposit,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+
+        // Step 2: An attested deposit mints to the recipient and is marked consumed
+        let deposit = DepositAttestation { source_chain_id: 1, tx_hash: [7; 32], recipient, amount: 500, nonce: 1 };
+        let tx = claim(deposit, recipient_token_account.pubkey(), recent_blockhash);
+        banks_client.process_transaction(tx).await.unwrap();
+        let account = banks_client.get_account(recipient_token_account.pubkey()).await.unwrap().unwrap();
+        assert_eq!(token_balance(&account.data), 500);
+        let account = banks_client.get_account(consumed_deposit(&deposit)).await.unwrap().unwrap();
+        let consumed = ConsumedDeposit::try_deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!((consumed.bridge, consumed.tx_hash, consumed.nonce), (bridge, deposit.tx_hash, deposit.nonce));
+
+        // Step 3: Claiming it again fails to create the consumed-deposit account and mints nothing
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let tx = claim(deposit, recipient_token_account.pubkey(), recent_blockhash);
+        let already_in_use = solana_sdk::system_instruction::SystemError::AccountAlreadyInUse as u32;
+        assert!(matches!(
+            banks_client.process_transaction(tx).await,
+            Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code))))
+                if code == already_in_use
+        ));
+        let account = banks_client.get_account(recipient_token_account.pubkey()).await.unwrap().unwrap();
+        assert_eq!(token_balance(&account.data), 500);
+
+        // Step 4: Tokens only go to an account owned by the attested recipient, and never for nothing
+        let next = DepositAttestation { nonce: 2, ..deposit };
+        let tx = claim(next, other_token_account.pubkey(), recent_blockhash);
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::InvalidDestination);
+        let tx = claim(DepositAttestation { amount: 0, ..next }, recipient_token_account.pubkey(), recent_blockhash);
+        assert_error(banks_client.process_transaction(tx).await, ErrorCode::InvalidAmount);
+    }
+
+    #[tokio::test]
+    async fn test_bonding() {
+        use anchor_spl::token::spl_token;
+        use solana_sdk::program_pack::Pack;
+
+        let program_id = crate::ID;
+        let mut context = start_program().await;
+        let payer = context.payer.insecure_clone();
+        let recent_blockhash = context.last_blockhash;
+        let config = config_address(program_id);
+        let bond_vault = Pubkey::find_program_address(&[BOND_VAULT_SEED, config.as_ref()], &program_id).0;
+        let token_balance = |data: &[u8]| spl_token::state::Account::unpack(data).unwrap().amount;
+
+        // Step 1: Create the bond token and fund an authority's token account
+        let mint = Keypair::new();
+        let authority = Keypair::new();
+        let authority_token_account = Keypair::new();
+        let rent = solana_sdk::rent::Rent::default();
+        let instructions = vec![
+            initialize_config_instruction(program_id, payer.pubkey(), RegistrationMode::Open),
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(&spl_token::id(), &mint.pubkey(), &payer.pubkey(), None, 0).unwrap(),
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &authority_token_account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &authority_token_account.pubkey(),
+                &mint.pubkey(),
+                &authority.pubkey(),
+            )
+            .unwrap(),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &authority_token_account.pubkey(),
+                &payer.pubkey(),
+                &[],
+                1_000,
+            )
+            .unwrap(),
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 1_000_000_000),
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(mint.pubkey(), false),
+                    AccountMeta::new(bond_vault, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::ConfigureBonding { min_bond: 100, unbonding_delay_slots: 10 }.data(),
+            },
+        ];
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &mint, &authority_token_account],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Step 2: Registering without the bond accounts fails, with them the bond is taken
+        let (oracle, _) = OracleAccount::find_address(&config, &authority.pubkey());
+        let private_key = PrivKey::from_random();
+        let g2_point_key = G2Point::try_from(&private_key).unwrap().0;
+        let pop_signature = proof_of_possession(&private_key);
+        let mut accounts = vec![
+            AccountMeta::new(oracle, false),
+            AccountMeta::new(authority.pubkey(), true),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            AccountMeta::new_readonly(config, false),
+        ];
+        let register = |accounts: Vec<AccountMeta>| {
+            Transaction::new_signed_with_payer(
+                &[Instruction { program_id, accounts, data: instruction::InitializeOracle { g2_point_key, pop_signature }.data() }],
+                Some(&payer.pubkey()),
+                &[&payer, &authority],
+                recent_blockhash,
+            )
+        };
+        assert!(context.banks_client.process_transaction(register(accounts.clone())).await.is_err());
+        accounts.extend([
+            AccountMeta::new_readonly(program_id, false), // No allowlist entry
+            AccountMeta::new_readonly(program_id, false), // No admin
+            AccountMeta::new(bond_vault, false),
+            AccountMeta::new(authority_token_account.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]);
+        context.banks_client.process_transaction(register(accounts)).await.unwrap();
+        let vault_data = context.banks_client.get_account(bond_vault).await.unwrap().unwrap();
+        assert_eq!(token_balance(&vault_data.data), 100);
+
+        // Step 3: Unbonded tokens can only be withdrawn after the delay
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(oracle, false),
+                    AccountMeta::new_readonly(authority.pubkey(), true),
+                ],
+                data: instruction::RequestUnbond { amount: 100 }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let withdraw = |recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(oracle, false),
+                        AccountMeta::new_readonly(authority.pubkey(), true),
+                        AccountMeta::new(authority_token_account.pubkey(), false),
+                        AccountMeta::new(bond_vault, false),
+                        AccountMeta::new_readonly(spl_token::id(), false),
+                    ],
+                    data: instruction::WithdrawUnbonded {}.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, &authority],
+                recent_blockhash,
+            )
+        };
+        assert!(context.banks_client.process_transaction(withdraw(recent_blockhash)).await.is_err());
+
+        context.warp_to_slot(100).unwrap();
+        let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        context.banks_client.process_transaction(withdraw(recent_blockhash)).await.unwrap();
+        let token_data = context
+            .banks_client
+            .get_account(authority_token_account.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(token_balance(&token_data.data), 1_000);
+
+        // Step 4: Once seated in a committee, the bond cannot be unbonded below the minimum
+        let bond_accounts = vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(oracle, false),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(authority_token_account.pubkey(), false),
+            AccountMeta::new(bond_vault, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+        let committee = Pubkey::find_program_address(&[COMMITTEE_SEED, config.as_ref(), &0u64.to_le_bytes()], &program_id).0;
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                Instruction { program_id, accounts: bond_accounts, data: instruction::Bond { amount: 150 }.data() },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(committee, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                        AccountMeta::new(oracle, false),
+                    ],
+                    data: instruction::InitializeCommittee {
+                        committee_id: 0,
+                        threshold: 1,
+                        handoff_window_slots: 1_000,
+                        snapshot_retention_epochs: 4,
+                    }
+                    .data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let unbond = |amount: u64, committee: Option<Pubkey>| {
+            let mut accounts = vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(oracle, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+            ];
+            accounts.extend(committee.map(|committee| AccountMeta::new_readonly(committee, false)));
+            Transaction::new_signed_with_payer(
+                &[Instruction { program_id, accounts, data: instruction::RequestUnbond { amount }.data() }],
+                Some(&payer.pubkey()),
+                &[&payer, &authority],
+                recent_blockhash,
+            )
+        };
+        assert_error(context.banks_client.process_transaction(unbond(100, None)).await, ErrorCode::CommitteeMismatch);
+        assert_error(
+            context.banks_client.process_transaction(unbond(100, Some(committee))).await,
+            ErrorCode::BondLockedInCommittee,
+        );
+        context.banks_client.process_transaction(unbond(50, Some(committee))).await.unwrap();
+        let account_data = context.banks_client.get_account(oracle).await.unwrap().unwrap();
+        let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(oracle_data.bonded, 100);
+
+        // Step 5: Delegations need the operator's consent, and the operator can release them
+        let reward_pool = reward_pool_address(&config);
+        let delegation =
+            Pubkey::find_program_address(&[DELEGATION_SEED, oracle.as_ref(), authority.pubkey().as_ref()], &program_id).0;
+        let delegate = |recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(oracle, false),
+                        AccountMeta::new(delegation, false),
+                        AccountMeta::new(authority.pubkey(), true),
+                        AccountMeta::new(authority_token_account.pubkey(), false),
+                        AccountMeta::new(bond_vault, false),
+                        AccountMeta::new_readonly(spl_token::id(), false),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::Delegate { amount: 10 }.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, &authority],
+                recent_blockhash,
+            )
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(reward_pool, false),
+                    AccountMeta::new(payer.pubkey(), true),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::InitializeRewardPool { fee_per_update: 0 }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        assert_error(context.banks_client.process_transaction(delegate(recent_blockhash)).await, ErrorCode::DelegationsClosed);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(reward_pool, false),
+                    AccountMeta::new(oracle, false),
+                    AccountMeta::new_readonly(authority.pubkey(), true),
+                ],
+                data: instruction::SetAcceptsDelegations { accepts_delegations: true }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let recent_blockhash = context.banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        context.banks_client.process_transaction(delegate(recent_blockhash)).await.unwrap();
+        let account_data = context.banks_client.get_account(delegation).await.unwrap().unwrap();
+        let delegation_data = Delegation::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!((delegation_data.amount, delegation_data.pending, delegation_data.pending_epoch), (0, 10, 1));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(reward_pool, false),
+                    AccountMeta::new(oracle, false),
+                    AccountMeta::new(delegation, false),
+                    AccountMeta::new(authority.pubkey(), false),
+                    AccountMeta::new_readonly(authority.pubkey(), true),
+                ],
+                data: instruction::ReleaseDelegation {}.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let account_data = context.banks_client.get_account(delegation).await.unwrap().unwrap();
+        let delegation_data = Delegation::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!((delegation_data.pending, delegation_data.unbonding[0].amount), (0, 10));
+        let account_data = context.banks_client.get_account(oracle).await.unwrap().unwrap();
+        let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!((oracle_data.delegated, oracle_data.pending_delegated, oracle_data.pending_delegations), (0, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_delegation() {
+        let program_id = crate::ID;
+        let mut context = start_program().await;
+        let payer = context.payer.insecure_clone();
+        let bonding = setup_bonding(&mut context, 100, 10).await;
+        let config = bonding.config;
+        let authority = Keypair::new();
+        let private_key = PrivKey::from_random();
+        let (oracle, _) = register_bonded_oracle(&mut context, &bonding, &authority, &private_key, 100).await;
+        let delegator = Keypair::new();
+        let delegator_token_account = create_token_account(&mut context, &bonding.mint, &delegator.pubkey(), 50).await;
+        let recent_blockhash = context.last_blockhash;
+        let committee = Pubkey::find_program_address(&[COMMITTEE_SEED, config.as_ref(), &0u64.to_le_bytes()], &program_id).0;
+        let reward_pool = reward_pool_address(&config);
+        let delegation =
+            Pubkey::find_program_address(&[DELEGATION_SEED, oracle.as_ref(), delegator.pubkey().as_ref()], &program_id).0;
+
+        // Step 1: Seat the oracle alone in a committee, fund a pool paying 1,500 lamports per
+        // update and open the oracle to delegations
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &delegator.pubkey(), 1_000_000_000),
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(committee, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                        AccountMeta::new(oracle, false),
+                    ],
+                    data: instruction::InitializeCommittee {
+                        committee_id: 0,
+                        threshold: 1,
+                        handoff_window_slots: 1_000,
+                        snapshot_retention_epochs: 4,
+                    }
+                    .data(),
+                },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::InitializeRewardPool { fee_per_update: 1_500 }.data(),
+                },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                    ],
+                    data: instruction::FundRewards { amount: 1_000_000 }.data(),
+                },
+                Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(oracle, false),
+                        AccountMeta::new_readonly(authority.pubkey(), true),
+                    ],
+                    data: instruction::SetAcceptsDelegations { accepts_delegations: true }.data(),
+                },
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let feed_id = [12u8; 32];
+        let (feed, history) = create_price_feed(
+            &mut context.banks_client,
+            &payer,
+            recent_blockhash,
+            config,
+            committee,
+            feed_id,
+            test_feed_settings(),
+        )
+        .await;
+
+        // Step 2: Delegated tokens wait for the next epoch before they earn
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(reward_pool, false),
+                    AccountMeta::new(oracle, false),
+                    AccountMeta::new(delegation, false),
+                    AccountMeta::new(delegator.pubkey(), true),
+                    AccountMeta::new(delegator_token_account, false),
+                    AccountMeta::new(bonding.bond_vault, false),
+                    AccountMeta::new_readonly(anchor_spl::token::ID, false),
+                    AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                ],
+                data: instruction::Delegate { amount: 50 }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer, &delegator],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let account_data = context.banks_client.get_account(delegation).await.unwrap().unwrap();
+        let delegation_data = Delegation::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!((delegation_data.amount, delegation_data.pending, delegation_data.pending_epoch), (0, 50, 1));
+
+        // Step 3: Over more rotations than checkpoints are kept, the oracle signs one paid update
+        // per epoch and its authority claims, crediting a third of each fee to the delegators
+        let account_data = context.banks_client.get_account(committee).await.unwrap().unwrap();
+        let member = Committee::try_deserialize(&mut &account_data.data[..]).unwrap().current.members[0].clone();
+        let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+        let rotations = MAX_WEIGHT_CHECKPOINTS as u64 + 1;
+        for epoch in 1..=rotations {
+            let next = vec![CommitteeMember { weight: 150, ..member.clone() }];
+            let report = PriceReport {
+                feed_id,
+                price: 100_000,
+                expo: -5,
+                publish_time: now - 100 + epoch as i64,
+                round_id: epoch,
+                ..PriceReport::default()
+            };
+            let report = report.try_to_vec().unwrap();
+            let recent_blockhash = context.banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+            let tx = Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new(committee, false),
+                            AccountMeta::new(oracle, false),
+                        ],
+                        data: instruction::RotateCommittee {
+                            threshold: 1,
+                            weights: vec![150],
+                            signer_bitmap: 0b1,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_key],
+                                &rotation_message(&committee, epoch, 1, &next),
+                            ),
+                        }
+                        .data(),
+                    },
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new_readonly(committee, false),
+                            AccountMeta::new_readonly(feed_registry_address(&config), false),
+                            AccountMeta::new(feed, false),
+                            AccountMeta::new(history, false),
+                            AccountMeta::new(reward_pool, false),
+                        ],
+                        data: instruction::UpdatePrice {
+                            epoch,
+                            signer_bitmap: 0b1,
+                            aggregated_and_compressed_g1_signature: sign_aggregate(
+                                &[&private_key],
+                                &price_report_message(&report),
+                            ),
+                            report,
+                        }
+                        .data(),
+                    },
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new(reward_pool, false),
+                            AccountMeta::new(oracle, false),
+                            AccountMeta::new(authority.pubkey(), true),
+                        ],
+                        data: instruction::ClaimRewards {}.data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer, &authority],
+                recent_blockhash,
+            );
+            context.banks_client.process_transaction(tx).await.unwrap();
+        }
+        let account_data = context.banks_client.get_account(oracle).await.unwrap().unwrap();
+        let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(oracle_data.weight_checkpoints.len(), MAX_WEIGHT_CHECKPOINTS);
+        assert_eq!(oracle_data.checkpoint_since(1).unwrap().epoch, 1);
+
+        // Step 4: The delegation earns from the epoch it was delegated for, not from the oldest
+        // checkpoint still kept
+        let manage = |data: Vec<u8>, recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(reward_pool, false),
+                        AccountMeta::new(oracle, false),
+                        AccountMeta::new(delegation, false),
+                        AccountMeta::new(delegator.pubkey(), true),
+                    ],
+                    data,
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, &delegator],
+                recent_blockhash,
+            )
+        };
+        let balance = context.banks_client.get_balance(delegator.pubkey()).await.unwrap();
+        let tx = manage(instruction::ClaimDelegationRewards {}.data(), recent_blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        assert_eq!(context.banks_client.get_balance(delegator.pubkey()).await.unwrap(), balance + rotations * 500);
+
+        // Step 5: Undelegated tokens can only be withdrawn after the delay, then the delegation closes
+        let tx = manage(instruction::Undelegate { amount: 51 }.data(), recent_blockhash);
+        assert_error(context.banks_client.process_transaction(tx).await, ErrorCode::InsufficientBond);
+        let tx = manage(instruction::Undelegate { amount: 50 }.data(), recent_blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let account_data = context.banks_client.get_account(oracle).await.unwrap().unwrap();
+        let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!(oracle_data.delegated, 0);
+
+        let withdraw = |recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(config, false),
+                        AccountMeta::new(delegation, false),
+                        AccountMeta::new(delegator.pubkey(), true),
+                        AccountMeta::new(delegator_token_account, false),
+                        AccountMeta::new(bonding.bond_vault, false),
+                        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+                    ],
+                    data: instruction::WithdrawUndelegated {}.data(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer, &delegator],
+                recent_blockhash,
+            )
+        };
+        assert_error(context.banks_client.process_transaction(withdraw(recent_blockhash)).await, ErrorCode::UnbondingPending);
+        context.warp_to_slot(100).unwrap();
+        let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        context.banks_client.process_transaction(withdraw(recent_blockhash)).await.unwrap();
+        assert_eq!(token_balance(&mut context, delegator_token_account).await, 50);
+        assert!(context.banks_client.get_account(delegation).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_slashing() {
+        let report = PriceReport { feed_id: [3; 32], price: 100, round_id: 7, ..PriceReport::default() };
+        let conflicting = PriceReport { price: 101, ..report };
+        let encode = |report: &PriceReport| report.try_to_vec().unwrap();
+        assert_eq!(check_equivocation(&encode(&report), &encode(&conflicting)).unwrap(), report);
+        assert!(check_equivocation(&encode(&report), &encode(&report)).is_err()); // Same report
+        let next_round = PriceReport { round_id: 8, ..conflicting };
+        assert!(check_equivocation(&encode(&report), &encode(&next_round)).is_err());
+        // Reports are signed under their own domain, never as an application message
+        assert_ne!(price_report_message(&encode(&report)), application_message(&encode(&report)));
+
+        // Half of 100 bonded plus 60 unbonding: the bond is emptied before the queue
+        let mut oracle = OracleAccount {
+            authority: Pubkey::default(),
+            g2_point_key: [0; 128],
+            bump: 0,
+            committee: None,
+            bonded: 100,
+            unbonding: vec![
+                UnbondingRequest { amount: 40, release_slot: 10 },
+                UnbondingRequest { amount: 20, release_slot: 20 },
+            ],
+            jailed: false,
+            delegated: 0,
+            commission_bps: 0,
+            operator_rewards: 0,
+            reward_per_share: 0,
+            weight_checkpoints: Vec::new(),
+            pending_delegated: 0,
+            pending_delegations: 0,
+            accepts_delegations: false,
+        };
+        assert_eq!(oracle.slash(5_000).unwrap(), 80);
+        assert_eq!(oracle.bonded, 20);
+        assert_eq!(oracle.unbonding.len(), 2);
+        assert_eq!(oracle.slash(10_000).unwrap(), 80);
+        assert_eq!(oracle.bonded, 0);
+        assert!(oracle.unbonding.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_slash_equivocation() {
+        let program_id = crate::ID;
+        let mut context = start_program().await;
+        let payer = context.payer.insecure_clone();
+        let bonding = setup_bonding(&mut context, 100, 10).await;
+        let config = bonding.config;
+        let authority = Keypair::new();
+        let private_key = PrivKey::from_random();
+        let (oracle, authority_token_account) =
+            register_bonded_oracle(&mut context, &bonding, &authority, &private_key, 100).await;
+        let treasury = create_token_account(&mut context, &bonding.mint, &payer.pubkey(), 0).await;
+        let reporter_token_account = create_token_account(&mut context, &bonding.mint, &payer.pubkey(), 0).await;
+        let recent_blockhash = context.last_blockhash;
+
+        // Step 1: Slash half of a bond and pay a fifth of it to the reporter
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(treasury, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true),
+                ],
+                data: instruction::SetSlashingParameters { slash_bps: 5_000, reporter_reward_bps: 2_000 }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let report = PriceReport { feed_id: [3; 32], price: 100, round_id: 7, ..PriceReport::default() };
+        let conflicting = PriceReport { price: 101, ..report };
+        let (report, conflicting) = (report.try_to_vec().unwrap(), conflicting.try_to_vec().unwrap());
+        let slash = |signature_a: [u8; 32], signature_b: [u8; 32], recent_blockhash| {
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    Instruction {
+                        program_id,
+                        accounts: vec![
+                            AccountMeta::new_readonly(config, false),
+                            AccountMeta::new(oracle, false),
+                            AccountMeta::new_readonly(program_id, false), // Not in a committee
+                            AccountMeta::new(bonding.bond_vault, false),
+                            AccountMeta::new(reporter_token_account, false),
+                            AccountMeta::new(treasury, false),
+                            AccountMeta::new_readonly(payer.pubkey(), true),
+                            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+                        ],
+                        data: instruction::SlashEquivocation {
+                            report_a: report.clone(),
+                            signature_a,
+                            report_b: conflicting.clone(),
+                            signature_b,
+                        }
+                        .data(),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            )
+        };
+
+        // Step 2: The same bytes signed as application messages are no evidence
+        let tx = slash(
+            sign_aggregate(&[&private_key], &application_message(&report)),
+            sign_aggregate(&[&private_key], &application_message(&conflicting)),
+            recent_blockhash,
+        );
+        assert_error(context.banks_client.process_transaction(tx).await, ErrorCode::SignatureVerificationFailed);
+
+        // Step 3: Two signed reports for the same feed and round slash and jail the oracle
+        let signature_a = sign_aggregate(&[&private_key], &price_report_message(&report));
+        let signature_b = sign_aggregate(&[&private_key], &price_report_message(&conflicting));
+        context.banks_client.process_transaction(slash(signature_a, signature_b, recent_blockhash)).await.unwrap();
+        let account_data = context.banks_client.get_account(oracle).await.unwrap().unwrap();
+        let oracle_data = OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap();
+        assert_eq!((oracle_data.bonded, oracle_data.jailed), (50, true));
+        assert_eq!(token_balance(&mut context, reporter_token_account).await, 10);
+        assert_eq!(token_balance(&mut context, treasury).await, 40);
+
+        // Step 4: A jailed oracle cannot be slashed again or sign
+        let recent_blockhash = context.banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let tx = slash(signature_a, signature_b, recent_blockhash);
+        assert_error(context.banks_client.process_transaction(tx).await, ErrorCode::OracleJailed);
+        let message = b"jailed".to_vec();
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: vec![AccountMeta::new_readonly(config, false), AccountMeta::new_readonly(oracle, false)],
+                data: instruction::VerifySignature {
+                    aggregated_and_compressed_g1_signature: sign_aggregate(&[&private_key], &application_message(&message)),
+                    message,
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert_error(context.banks_client.process_transaction(tx).await, ErrorCode::OracleJailed);
+
+        // Step 5: Nor can it unbond, withdraw or deregister to register again unjailed
+        let unbond = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(oracle, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+            ],
+            data: instruction::RequestUnbond { amount: 50 }.data(),
+        };
+        let withdraw = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new(oracle, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new(authority_token_account, false),
+                AccountMeta::new(bonding.bond_vault, false),
+                AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            ],
+            data: instruction::WithdrawUnbonded {}.data(),
+        };
+        let deregister = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(oracle, false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(config, false),
+            ],
+            data: instruction::DeregisterOracle {}.data(),
+        };
+        for instruction in [unbond, withdraw, deregister] {
+            let tx = Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&payer.pubkey()),
+                &[&payer, &authority],
+                recent_blockhash,
+            );
+            assert_error(context.banks_client.process_transaction(tx).await, ErrorCode::OracleJailed);
+        }
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_oracle_instruction(oracle, authority.pubkey(), &private_key)],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+        assert!(context.banks_client.process_transaction(tx).await.is_err());
+        let account_data = context.banks_client.get_account(oracle).await.unwrap().unwrap();
+        assert!(OracleAccount::try_deserialize(&mut &account_data.data[..]).unwrap().jailed);
+    }
+
+    #[test]
+    fn test_reward_credit() {
+        let oracles: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let set = CommitteeSet {
+            epoch: 0,
+            threshold: 1,
+            aggregate_key: [0; 128],
+            quorum_bps: 0,
+            members: oracles
+                .iter()
+                .map(|oracle| CommitteeMember { oracle: *oracle, g2_point_key: [0; 128], weight: 0 })
+                .collect(),
+        };
+        let mut pool = RewardPool {
+            config: Pubkey::new_unique(),
+            fee_per_update: 1_000,
+            available: 1_500,
+            balances: Vec::new(),
+            bump: 0,
+        };
+
+        // 1000 split three ways, the remainder stays available
+        pool.credit(&set, 0b111).unwrap();
+        assert_eq!(pool.balances.len(), 3);
+        assert!(pool.balances.iter().all(|balance| balance.owed == 333));
+        assert_eq!(pool.available, 501);
+
+        // Only what is funded is paid out
+        pool.credit(&set, 0b001).unwrap();
+        assert_eq!(pool.take_balance(&oracles[0]), 333 + 501);
+        assert_eq!(pool.available, 0);
+        assert_eq!(pool.take_balance(&oracles[0]), 0);
+
+        // An empty pool credits nothing
+        pool.credit(&set, 0b110).unwrap();
+        assert_eq!(pool.take_balance(&oracles[1]), 333);
+    }
+
+    #[test]
+    fn test_delegation_rewards() {
+        let mut oracle = OracleAccount {
+            authority: Pubkey::default(),
+            g2_point_key: [0; 128],
+            bump: 0,
+            committee: None,
+            bonded: 100,
+            unbonding: Vec::new(),
+            jailed: false,
+            delegated: 300,
+            commission_bps: 1_000,
+            operator_rewards: 0,
+            reward_per_share: 0,
+            weight_checkpoints: vec![WeightCheckpoint { epoch: 0, weight: 400, reward_per_share: 0, pending_delegations: 0 }],
+            pending_delegated: 0,
+            pending_delegations: 0,
+            accepts_delegations: true,
+        };
+        let delegation = |amount: u64| Delegation {
+            oracle: Pubkey::default(),
+            delegator: Pubkey::default(),
+            amount,
+            reward_debt: 0,
+            unbonding: Vec::new(),
+            bump: 0,
+            pending: 0,
+            pending_epoch: 0,
+        };
+        let (mut first, mut second) = (delegation(100), delegation(200));
+
+        // 3/4 of 4000 goes to delegators, less a 10% commission
+        oracle.accrue_rewards(4_000).unwrap();
+        assert_eq!(oracle.operator_rewards, 1_000 + 300);
+        assert_eq!(first.pending_rewards(oracle.reward_per_share).unwrap(), 900);
+        assert_eq!(second.pending_rewards(oracle.reward_per_share).unwrap(), 1_800);
+
+        // Tokens delegated after the checkpoint earn nothing until the next one counts them
+        first.reset_reward_debt(oracle.reward_per_share).unwrap();
+        second.reset_reward_debt(oracle.reward_per_share).unwrap();
+        second.pending = 100;
+        second.pending_epoch = 1;
+        oracle.delegated += 100;
+        oracle.pending_delegated += 100;
+        oracle.pending_delegations += 1;
+        oracle.commission_bps = 0;
+        oracle.accrue_rewards(1_000).unwrap();
+        assert_eq!(oracle.operator_rewards, 1_300 + 250);
+        assert_eq!(first.pending_rewards(oracle.reward_per_share).unwrap(), 250);
+        assert_eq!(second.pending_rewards(oracle.reward_per_share).unwrap(), 500);
+        assert_eq!(second.activate_pending(&mut oracle).unwrap(), 0);
+        assert_eq!(second.pending, 100);
+
+        // From epoch 1 they earn, and are paid from the checkpoint on once activated
+        assert_eq!(oracle.checkpoint_weight(1, None).unwrap(), 500);
+        assert_eq!((oracle.pending_delegated, oracle.pending_delegations), (0, 0));
+        assert_eq!(oracle.checkpoint_since(1).unwrap().pending_delegations, 1);
+        oracle.accrue_rewards(1_000).unwrap();
+        assert_eq!(first.pending_rewards(oracle.reward_per_share).unwrap(), 450);
+        assert_eq!(second.pending_rewards(oracle.reward_per_share).unwrap(), 900);
+        assert_eq!(second.activate_pending(&mut oracle).unwrap(), 200);
+        assert_eq!((second.amount, second.pending), (300, 0));
+        assert_eq!(oracle.checkpoint_since(1).unwrap().pending_delegations, 0);
+
+        // Undelegated tokens stop earning; their share goes to the operator
+        oracle.delegated = 0;
+        let operator_rewards = oracle.operator_rewards;
+        oracle.accrue_rewards(1_000).unwrap();
+        assert_eq!(oracle.operator_rewards, operator_rewards + 1_000);
+
+        // Only the latest epochs are kept
+        for epoch in 2..=MAX_WEIGHT_CHECKPOINTS as u64 {
+            oracle.checkpoint_weight(epoch, None).unwrap();
+        }
+        assert_eq!(oracle.checkpoint_since(0).unwrap().epoch, 1);
+        assert_eq!(oracle.checkpoint_since(2).unwrap().weight, 100);
+        assert!(oracle.checkpoint_since(MAX_WEIGHT_CHECKPOINTS as u64 + 1).is_none());
+
+        // Unless a delegation pending since then still starts earning at it
+        let start = MAX_WEIGHT_CHECKPOINTS as u64 + 1;
+        let mut third = delegation(0);
+        third.pending = 50;
+        third.pending_epoch = start;
+        oracle.delegated = 50;
+        oracle.pending_delegated = 50;
+        oracle.pending_delegations = 1;
+        for epoch in start..=start + MAX_WEIGHT_CHECKPOINTS as u64 {
+            assert_eq!(oracle.checkpoint_weight(epoch, None).unwrap(), 150);
+            oracle.accrue_rewards(1_500).unwrap();
+        }
+        assert_eq!(oracle.weight_checkpoints.len(), MAX_WEIGHT_CHECKPOINTS);
+        assert_eq!(oracle.weight_checkpoints[0].epoch, start);
+        assert_eq!(third.activate_pending(&mut oracle).unwrap(), 9 * 500);
+        assert_eq!(oracle.weight_checkpoints[0].pending_delegations, 0);
+
+        // It is dropped like any other once every kept checkpoint has pending delegations,
+        // which then start earning at the next one
+        oracle.weight_checkpoints.iter_mut().for_each(|checkpoint| checkpoint.pending_delegations = 1);
+        oracle.checkpoint_weight(start + MAX_WEIGHT_CHECKPOINTS as u64 + 1, None).unwrap();
+        assert_eq!(oracle.weight_checkpoints[0].epoch, start + 2);
+        assert_eq!(oracle.weight_checkpoints[0].pending_delegations, 2);
+    }
+
+    #[test]
+    fn test_governance_message() {
+        let (config, committee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let proposal = GovernanceProposal { nonce: 0, expiry_slot: 100, action: GovernanceAction::SetPaused { paused: true } };
+        let message = governance_message(&config, &committee, &proposal).unwrap();
+        assert_eq!(message, governance_message(&config, &committee, &proposal).unwrap());
+
+        // Nonce, expiry, action, config and committee are all bound
+        let others = [
+            GovernanceProposal { nonce: 1, ..proposal },
+            GovernanceProposal { expiry_slot: 101, ..proposal },
+            GovernanceProposal { action: GovernanceAction::SetPaused { paused: false }, ..proposal },
+        ];
+        for other in others {
+            assert_ne!(governance_message(&config, &committee, &other).unwrap(), message);
+        }
+        assert_ne!(governance_message(&committee, &config, &proposal).unwrap(), message);
+    }
+
+    #[test]
+    fn test_feed_registry_unique_ids() {
+        let (config, committee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut registry = FeedRegistry { config, feeds: Vec::new(), bump: 0 };
+        let policy = FeedPolicy {
+            feed_id: [1; 32],
+            committee,
+            settings: FeedSettings::default(),
+            retired: false,
+            frozen: false,
+            frozen_slot: 0,
+        };
+        registry.register(policy).unwrap();
+        assert!(registry.register(policy).is_err());
+    }
+
+    #[test]
+    fn test_vault_transfer_message() {
+        let vault = Pubkey::new_unique();
+        let intent = TransferIntent {
+            destination: Pubkey::new_unique(),
+            mint: None,
+            amount: 1_000,
+            nonce: 0,
+            expiry_slot: 100,
+        };
+        let message = vault_transfer_message(&vault, &intent).unwrap();
+        let others = [
+            TransferIntent { destination: Pubkey::new_unique(), ..intent },
+            TransferIntent { mint: Some(Pubkey::new_unique()), ..intent },
+            TransferIntent { amount: 1_001, ..intent },
+            TransferIntent { nonce: 1, ..intent },
+        ];
+        for other in others {
+            assert_ne!(vault_transfer_message(&vault, &other).unwrap(), message);
+        }
+        assert_ne!(vault_transfer_message(&Pubkey::new_unique(), &intent).unwrap(), message);
+    }
+
+    #[test]
+    fn test_bridge_deposit_message() {
+        let bridge = Pubkey::new_unique();
+        let deposit = DepositAttestation {
+            source_chain_id: 1,
+            tx_hash: [9; 32],
+            recipient: Pubkey::new_unique(),
+            amount: 5_000,
+            nonce: 3,
+        };
+        let message = bridge_deposit_message(&bridge, &deposit).unwrap();
+        let others = [
+            DepositAttestation { source_chain_id: 2, ..deposit },
+            DepositAttestation { tx_hash: [8; 32], ..deposit },
+            DepositAttestation { recipient: Pubkey::new_unique(), ..deposit },
+            DepositAttestation { amount: 5_001, ..deposit },
+            DepositAttestation { nonce: 4, ..deposit },
+        ];
+        for other in others {
+            assert_ne!(bridge_deposit_message(&bridge, &other).unwrap(), message);
+        }
+        assert_ne!(bridge_deposit_message(&Pubkey::new_unique(), &deposit).unwrap(), message);
+    }
+
+    #[test]
+    fn test_initialize_and_verify_signature_locally() {
+        // Step 1: Generate private keys and derive G2 points
+        let private_key_1 = PrivKey::from_random();
+        let private_key_2 = PrivKey::from_random();
+
+        let g2_point_1 = G2Point::try_from(&private_key_1).expect("Failed to derive G2 point from private key 1");
+        let g2_point_2 = G2Point::try_from(&private_key_2).expect("Failed to derive G2 point from private key 2");
+
+        // Step 2: Aggregate G2 points
+        let aggregated_g2_point = g2_point_1 + g2_point_2;
+
+        // Verify aggregation
+        // println!("✅ Aggregated G2 Point: {:?}", aggregated_g2_point);
+
+        // Step 3: Generate an aggregated G1 signature (off-chain)
+        let message = b"Test message".to_vec();
+        let g1_signature_1 = private_key_1.sign::<Sha256Normalized, &[u8]>(&message).unwrap();
+        let g1_signature_2 =private_key_2.sign::<Sha256Normalized, &[u8]>(&message).unwrap();
+
+        // Aggregate G1 signatures (using point addition for simplicity)
+        let aggregated_g1_signature = g1_signature_1 + g1_signature_2;
+        let compressed_g1_agg_sig = G1CompressedPoint::try_from(aggregated_g1_signature).expect("Failed to compress aggregated G1 signature");
+        // Verify G1 signature aggregation
+        // println!("✅ Aggregated G1 Signature: {:?}", aggregated_g1_signature);
+
+        // Step 4: Verify the aggregated signature
+        let aggregated_g2_compressed = G2CompressedPoint::try_from(&aggregated_g2_point)
+            .expect("Failed to compress aggregated G2 point");
+    
+        match aggregated_g2_compressed.verify_signature::<Sha256Normalized, &[u8], G1CompressedPoint>(
+            compressed_g1_agg_sig,
+            &message,
+        ) {
+            Ok(_) => println!("✅ Signature verified successfully!"),
+            Err(err) => panic!("❌ Signature verification failed: {:?}", err),
+        }
+    }
+
+//     #[test]
+// fn test_initialize_and_verify_signature_with_five_keys() {
+    // Step 1: Generate private keys and derive G2 points
+//     let private_keys: Vec<PrivKey> = (0..5)
+//         .map(|_| PrivKey::from_random())
+//         .collect();
+
+//     let g2_points: Vec<G2Point> = private_keys
+//         .iter()
+//         .map(|key| G2Point::try_from(key).expect("Failed to derive G2 point from private key"))
+//         .collect();
+
+//     // Step 2: Aggregate G2 points
+//     // Step 2: Aggregate G2 points
+//     let mut aggregated_g2_point = g2_points[0].clone(); // Start with the first valid G2 point
+//     for g2_point in g2_points.iter().skip(1) {
+//         aggregated_g2_point = aggregated_g2_point + g2_point.clone();
+//     }
+
+//     // Verify aggregation
+//     // println!("✅ Aggregated G2 Point: {:?}", aggregated_g2_point);
+
+//     // Step 3: Generate an aggregated G1 signature (off-chain)
+//     let message = b"Test message".to_vec();
+//     let g1_signatures: Vec<G1Point> = private_keys
+//         .iter()
+//         .map(|key| {
+//             key.sign::<Sha256Normalized, &[u8]>(&message)
+//                 .expect("Failed to sign message with private key")
+//         })
+//         .collect();
+
+//     let mut aggregated_g1_signature = G1Point([0u8; 64]); // Start with a zeroed G1 signature
+//     for g1_signature in g1_signatures {
+//         aggregated_g1_signature = aggregated_g1_signature + g1_signature;
+//     }
+
+//     // Step 4: Verify the aggregated signature
+//     let aggregated_g2_compressed = G2CompressedPoint::try_from(&aggregated_g2_point)
+//         .expect("Failed to compress aggregated G2 point");
+
+//     match aggregated_g2_compressed.verify_signature::<Sha256Normalized, _, _>(
+//         aggregated_g1_signature,
+//         &message,
+//     ) {
+//         Ok(_) => println!("✅ Signature verified successfully with 5 keys!"),
+//         Err(err) => panic!("❌ Signature verification failed: {:?}", err),
+//     }
+// }
 }
\ No newline at end of file
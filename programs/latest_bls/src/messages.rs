@@ -0,0 +1,104 @@
+//! The domain-separated messages signers sign for each instruction.
+
+use crate::*;
+
+/// Domain separator for the message an oracle signs to prove possession of its BLS key.
+pub const POP_DOMAIN: &[u8] = b"latest_bls:proof_of_possession";
+
+/// Domain separator for the message an outgoing committee signs to approve the next one.
+pub const ROTATION_DOMAIN: &[u8] = b"latest_bls:rotate_committee";
+
+/// Domain separator for the message a committee member signs over its own price observation.
+pub const OBSERVATION_DOMAIN: &[u8] = b"latest_bls:observation";
+
+pub const PRICE_ROOT_DOMAIN: &[u8] = b"latest_bls:price_root";
+
+/// Domain separator for the message a committee or oracle signs over a Borsh-encoded `PriceReport`.
+pub const PRICE_REPORT_DOMAIN: &[u8] = b"latest_bls:price_report";
+
+/// Domain separator for the message a committee signs over a Borsh-encoded `TimestampedMessage`.
+pub const TIMESTAMPED_DOMAIN: &[u8] = b"latest_bls:timestamped";
+
+/// Domain separator for application messages checked by the generic verification instructions.
+pub const MESSAGE_DOMAIN: &[u8] = b"latest_bls:message";
+
+/// Domain separator for the message a governance committee signs to authorize an action.
+pub const GOVERNANCE_DOMAIN: &[u8] = b"latest_bls:governance";
+
+/// Domain separator for the message a committee signs to spend from its vault.
+pub const VAULT_TRANSFER_DOMAIN: &[u8] = b"latest_bls:vault_transfer";
+
+/// Domain separator for the message a committee signs to attest a deposit on another chain.
+pub const BRIDGE_DEPOSIT_DOMAIN: &[u8] = b"latest_bls:bridge_deposit";
+
+/// The message a BLS key signs to prove possession of itself.
+pub fn proof_of_possession_message(g2_point_key: &[u8; 128]) -> [u8; 32] {
+    hashv(&[POP_DOMAIN, g2_point_key]).to_bytes()
+}
+
+/// The message an outgoing committee signs to hand `committee` over to `next_epoch`: a hash of
+/// the committee address, the next epoch and threshold, and the hash of the next keys and weights.
+pub fn rotation_message(
+    committee: &Pubkey,
+    next_epoch: u64,
+    threshold: u16,
+    members: &[CommitteeMember],
+) -> [u8; 32] {
+    let keys: Vec<u8> = members
+        .iter()
+        .flat_map(|member| member.g2_point_key.into_iter().chain(member.weight.to_le_bytes()))
+        .collect();
+    let key_set_hash = hash(&keys);
+    hashv(&[
+        ROTATION_DOMAIN,
+        committee.as_ref(),
+        &next_epoch.to_le_bytes(),
+        &threshold.to_le_bytes(),
+        key_set_hash.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// The message a committee signs to attest `root` as a batch of price reports.
+pub fn price_root_message(committee: &Pubkey, root: &[u8; 32]) -> [u8; 32] {
+    hashv(&[PRICE_ROOT_DOMAIN, committee.as_ref(), root]).to_bytes()
+}
+
+/// The message a committee, or one of its members, signs over a Borsh-encoded `PriceReport`.
+pub fn price_report_message(report: &[u8]) -> [u8; 32] {
+    hashv(&[PRICE_REPORT_DOMAIN, report]).to_bytes()
+}
+
+/// The message a committee signs over a Borsh-encoded `TimestampedMessage`.
+pub fn timestamped_message(envelope: &[u8]) -> [u8; 32] {
+    hashv(&[TIMESTAMPED_DOMAIN, envelope]).to_bytes()
+}
+
+/// The message signers sign over an application `message` checked by the generic verification
+/// instructions, so that no application message can pass for one of the program's own.
+pub fn application_message(message: &[u8]) -> [u8; 32] {
+    hashv(&[MESSAGE_DOMAIN, message]).to_bytes()
+}
+
+/// The message the committee member `oracle` signs over its own `observation`.
+pub fn observation_message(oracle: &Pubkey, observation: &[u8]) -> [u8; 32] {
+    hashv(&[OBSERVATION_DOMAIN, oracle.as_ref(), observation]).to_bytes()
+}
+
+/// The message a governance committee signs to authorize `proposal` for the program `config`.
+pub fn governance_message(config: &Pubkey, committee: &Pubkey, proposal: &GovernanceProposal) -> Result<[u8; 32]> {
+    let proposal = proposal.try_to_vec()?;
+    Ok(hashv(&[GOVERNANCE_DOMAIN, config.as_ref(), committee.as_ref(), &proposal]).to_bytes())
+}
+
+/// The message a committee signs to authorize `intent` from the vault `vault`.
+pub fn vault_transfer_message(vault: &Pubkey, intent: &TransferIntent) -> Result<[u8; 32]> {
+    let intent = intent.try_to_vec()?;
+    Ok(hashv(&[VAULT_TRANSFER_DOMAIN, vault.as_ref(), &intent]).to_bytes())
+}
+
+/// The message a committee signs to attest `deposit` to the bridge `bridge`.
+pub fn bridge_deposit_message(bridge: &Pubkey, deposit: &DepositAttestation) -> Result<[u8; 32]> {
+    let deposit = deposit.try_to_vec()?;
+    Ok(hashv(&[BRIDGE_DEPOSIT_DOMAIN, bridge.as_ref(), &deposit]).to_bytes())
+}
@@ -0,0 +1,1107 @@
+//! Accounts and data types stored by the program.
+
+use crate::*;
+
+/// Who may register an oracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RegistrationMode {
+    Open,      // Anyone may register
+    Allowlist, // Only authorities with an allowlist entry may register
+    AdminOnly, // The admin must co-sign every registration
+}
+
+/// Data structure for the program configuration.
+///
+/// `paused` blocks the oracle, verification and update instructions. Slashing an equivocating
+/// oracle, freezing an equivocating committee and challenging an optimistic report stay ungated,
+/// so misbehaviour can still be punished and disputes stay open for the whole dispute window.
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,        // Admin nominated by `propose_admin`
+    pub registration_mode: RegistrationMode,  // Who may register oracles
+    pub max_committee_size: u16,              // Maximum number of oracles aggregated in one verification
+    pub paused: bool,                         // Blocks oracle and verification instructions when set
+    pub bond_mint: Option<Pubkey>,            // Token oracles must bond, once bonding is enabled
+    pub min_bond: u64,                        // Bond required to register and join committees
+    pub unbonding_delay_slots: u64,           // Slots between an unbond request and its withdrawal
+    pub bond_vault_bump: u8,                  // Bump of the bond vault PDA
+    pub slash_bps: u16,                       // Share of the bond slashed for equivocation
+    pub reporter_reward_bps: u16,             // Share of a slashed amount paid to the reporter
+    pub treasury: Option<Pubkey>,             // Token account receiving the rest of slashed bonds
+    pub optimistic_bond: u64,                 // Lamports bonded on each optimistic report
+    pub dispute_window_slots: u64,            // Slots an optimistic report can be disputed for, 0 until enabled
+    pub governance_committee: Option<Pubkey>, // Committee allowed to sign governance actions
+    pub governance_nonce: u64,                // Nonce the next governance proposal must carry
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    pub const LEN: usize =
+        32 + (1 + 32) + 1 + 2 + 1 + (1 + 32) + 8 + 8 + 1 + 2 + 2 + (1 + 32) + 8 + 8 + (1 + 32) + 8 + 1;
+
+    /// Fail once a governance committee is set: the changes it can authorize are then only made
+    /// through `execute_governance`, and the remaining admin changes are closed.
+    pub fn check_admin_governs(&self) -> Result<()> {
+        require!(self.governance_committee.is_none(), ErrorCode::GovernedByCommittee);
+        Ok(())
+    }
+}
+
+/// A member of a committee.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CommitteeMember {
+    pub oracle: Pubkey,          // Oracle PDA of the member
+    pub g2_point_key: [u8; 128], // Member's G2 public key
+    pub weight: u64,             // Oracle's bond when the set was formed
+}
+
+impl CommitteeMember {
+    pub const LEN: usize = 32 + 128 + 8;
+}
+
+/// The members of a committee for one epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CommitteeSet {
+    pub epoch: u64,                    // Epoch this set is valid for
+    pub threshold: u16,                // Minimum number of signers
+    pub aggregate_key: [u8; 128],      // Sum of every member's G2 public key
+    pub quorum_bps: u16,               // Share of the total weight that must sign, 0 to disable
+    pub members: Vec<CommitteeMember>, // Members, indexed by signer bitmap bit
+}
+
+impl CommitteeSet {
+    /// Serialized size of a set with `members` members.
+    pub const fn space(members: usize) -> usize {
+        8 + 2 + 128 + 2 + 4 + members * CommitteeMember::LEN
+    }
+
+    /// Build a set, checking the threshold and computing the aggregate key.
+    pub fn new(epoch: u64, threshold: u16, members: Vec<CommitteeMember>) -> Result<Self> {
+        require!(
+            threshold > 0 && threshold as usize <= members.len(),
+            ErrorCode::InvalidThreshold
+        );
+        let aggregate_key = aggregate_keys(members.iter().map(|member| &member.g2_point_key))?.0;
+        Ok(Self { epoch, threshold, aggregate_key, quorum_bps: 0, members })
+    }
+
+    /// Remove `oracle` from the set if present and recompute the aggregate key.
+    pub fn remove_member(&mut self, oracle: &Pubkey) {
+        let before = self.members.len();
+        self.members.retain(|member| member.oracle != *oracle);
+        if self.members.len() != before {
+            self.aggregate_key = aggregate_keys(self.members.iter().map(|member| &member.g2_point_key))
+                .map(|aggregated| aggregated.0)
+                .unwrap_or([0u8; 128]); // Empty set, nothing left to aggregate
+        }
+    }
+
+    /// Check `signer_bitmap` only selects members and meets the signer threshold and weight quorum,
+    /// returning the signer count.
+    fn check_bitmap(&self, signer_bitmap: u32) -> Result<u16> {
+        let all_members = (1u64 << self.members.len()) - 1;
+        require!(
+            !self.members.is_empty() && u64::from(signer_bitmap) & !all_members == 0,
+            ErrorCode::InvalidSignerBitmap
+        );
+        let signers = signer_bitmap.count_ones() as u16;
+        require!(signers >= self.threshold, ErrorCode::ThresholdNotMet);
+
+        if self.quorum_bps > 0 {
+            let (mut signing_weight, mut total_weight) = (0u128, 0u128);
+            for (index, member) in self.members.iter().enumerate() {
+                total_weight += u128::from(member.weight);
+                if signer_bitmap & (1 << index) != 0 {
+                    signing_weight += u128::from(member.weight);
+                }
+            }
+            require!(
+                signing_weight * 10_000 >= total_weight * u128::from(self.quorum_bps),
+                ErrorCode::ThresholdNotMet
+            );
+        }
+        Ok(signers)
+    }
+
+    /// Verify a signature by the members selected in `signer_bitmap`, returning the signer count.
+    pub fn verify(&self, signer_bitmap: u32, signature: [u8; 32], message: &[u8]) -> Result<u16> {
+        let signers = self.check_bitmap(signer_bitmap)?;
+        let all_members = (1u64 << self.members.len()) - 1;
+
+        // Everyone signed: reuse the stored aggregate key instead of summing again
+        let aggregated_g2_point = if u64::from(signer_bitmap) == all_members {
+            G2Point(self.aggregate_key)
+        } else {
+            aggregate_keys(
+                self.members
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| signer_bitmap & (1 << index) != 0)
+                    .map(|(_, member)| &member.g2_point_key),
+            )?
+        };
+        check_signature(aggregated_g2_point, G1CompressedPoint(signature), message)?;
+        Ok(signers)
+    }
+
+    /// Verify an aggregate of signatures where each member selected in `signer_bitmap` signed
+    /// `observation_message` over its own observation, given in member order. Binding the
+    /// signer into the message keeps identical observations from collapsing into one message.
+    /// Returns the signer count.
+    pub fn verify_distinct(&self, signer_bitmap: u32, signature: [u8; 32], observations: &[Vec<u8>]) -> Result<u16> {
+        let signers = self.check_bitmap(signer_bitmap)?;
+        let members: Vec<&CommitteeMember> = self
+            .members
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| signer_bitmap & (1 << index) != 0)
+            .map(|(_, member)| member)
+            .collect();
+        require!(members.len() == observations.len(), ErrorCode::InvalidSignerBitmap);
+        let keys: Vec<&[u8; 128]> = members.iter().map(|member| &member.g2_point_key).collect();
+        let messages: Vec<Vec<u8>> = members
+            .iter()
+            .zip(observations)
+            .map(|(member, observation)| observation_message(&member.oracle, observation).to_vec())
+            .collect();
+        check_distinct_signatures(&keys, &messages, G1CompressedPoint(signature))?;
+        Ok(signers)
+    }
+}
+
+/// Data structure for a committee that rotates by epoch.
+#[account]
+pub struct Committee {
+    pub config: Pubkey,                  // Registry the committee belongs to
+    pub committee_id: u64,               // Identifier within the registry
+    pub current: CommitteeSet,           // Members for the current epoch
+    pub previous: Option<CommitteeSet>,  // Members for the previous epoch, if any
+    pub previous_valid_until: u64,       // Last slot at which the previous epoch may still verify
+    pub handoff_window_slots: u64,       // Slots the previous epoch stays usable after a rotation
+    pub snapshot_retention_epochs: u64,  // Epochs a snapshot is kept before it may be closed
+    pub max_staleness: i64,              // Oldest accepted timestamped message age in seconds, 0 to disable
+    pub max_future_skew: i64,            // Furthest accepted message time ahead of the clock, 0 to disable
+    pub frozen: bool,                    // Set after equivocation; frozen committees cannot verify
+    pub frozen_slot: u64,                // Slot of the latest freeze, 0 if never frozen
+    pub bump: u8,
+}
+
+impl Committee {
+    pub const LEN: usize = 32
+        + 8
+        + CommitteeSet::space(MAX_COMMITTEE_MEMBERS)
+        + (1 + CommitteeSet::space(MAX_COMMITTEE_MEMBERS))
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + 1;
+
+    /// The member set for `epoch` if it is the current or previous epoch, regardless of the
+    /// handoff window.
+    pub fn set_to_snapshot(&self, epoch: u64) -> Option<&CommitteeSet> {
+        if epoch == self.current.epoch {
+            return Some(&self.current);
+        }
+        self.previous.as_ref().filter(|previous| previous.epoch == epoch)
+    }
+
+    /// Whether `oracle` is a member of the current epoch, or of the previous one while it may
+    /// still verify at `slot`.
+    pub fn seats(&self, oracle: &Pubkey, slot: u64) -> bool {
+        let in_set = |set: &CommitteeSet| set.members.iter().any(|member| member.oracle == *oracle);
+        in_set(&self.current) || (slot <= self.previous_valid_until && self.previous.as_ref().is_some_and(in_set))
+    }
+
+    /// The member set that signs for `epoch`, if it is still usable at `slot`.
+    pub fn set_for_epoch(&self, epoch: u64, slot: u64) -> Result<&CommitteeSet> {
+        require!(!self.frozen, ErrorCode::CommitteeFrozen);
+        if epoch == self.current.epoch {
+            return Ok(&self.current);
+        }
+        match &self.previous {
+            Some(previous) if previous.epoch == epoch => {
+                require!(slot <= self.previous_valid_until, ErrorCode::EpochExpired);
+                Ok(previous)
+            }
+            _ => err!(ErrorCode::UnknownEpoch),
+        }
+    }
+
+    /// Verify a signature by `set`, one of this committee's member sets or a snapshot of one.
+    /// Every committee signature is checked here, so a frozen committee verifies nothing.
+    pub fn verify_set(
+        &self,
+        set: &CommitteeSet,
+        signer_bitmap: u32,
+        signature: [u8; 32],
+        message: &[u8],
+    ) -> Result<u16> {
+        require!(!self.frozen, ErrorCode::CommitteeFrozen);
+        set.verify(signer_bitmap, signature, message)
+    }
+
+    /// Verify a signature by the member set for `epoch`, if it is still usable at `slot`.
+    pub fn verify(&self, epoch: u64, slot: u64, signer_bitmap: u32, signature: [u8; 32], message: &[u8]) -> Result<u16> {
+        self.verify_set(self.set_for_epoch(epoch, slot)?, signer_bitmap, signature, message)
+    }
+
+    /// Check that a signature verified at `attested_slot` can still be relied on: the committee
+    /// is not frozen and has not been frozen since.
+    pub fn check_attested_at(&self, attested_slot: u64) -> Result<()> {
+        require!(!self.frozen, ErrorCode::CommitteeFrozen);
+        require!(attested_slot > self.frozen_slot, ErrorCode::AttestedBeforeFreeze);
+        Ok(())
+    }
+}
+
+/// Data structure for an immutable copy of a committee's member set at one epoch.
+#[account]
+pub struct CommitteeSnapshot {
+    pub committee: Pubkey,  // Committee the snapshot was taken from
+    pub set: CommitteeSet,  // Member set, epoch and aggregate key at that epoch
+    pub created_slot: u64,  // Slot the snapshot was taken
+    pub rent_payer: Pubkey, // Paid for the snapshot, receives the rent on close
+    pub bump: u8,
+}
+
+impl CommitteeSnapshot {
+    /// Account size for a snapshot of `members` members.
+    pub const fn space(members: usize) -> usize {
+        32 + CommitteeSet::space(members) + 8 + 32 + 1
+    }
+}
+
+/// Data structure for an allowlist entry.
+#[account]
+pub struct AllowlistEntry {
+    pub authority: Pubkey, // Authority allowed to register an oracle
+    pub bump: u8,
+}
+
+impl AllowlistEntry {
+    pub const LEN: usize = 32 + 1;
+}
+
+/// Bonded tokens queued for withdrawal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UnbondingRequest {
+    pub amount: u64,       // Tokens to withdraw
+    pub release_slot: u64, // First slot the tokens may be withdrawn
+}
+
+impl UnbondingRequest {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// An oracle's weight when a committee set including it was formed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WeightCheckpoint {
+    pub epoch: u64,               // Committee epoch
+    pub weight: u64,              // Bond plus delegations at that epoch
+    pub reward_per_share: u128,   // Delegator rewards per share when the checkpoint was taken
+    pub pending_delegations: u32, // Delegations yet to settle that start earning at this checkpoint
+}
+
+impl WeightCheckpoint {
+    pub const LEN: usize = 8 + 8 + 16 + 4;
+}
+
+/// Data structure for an Oracle account.
+#[account]
+pub struct OracleAccount {
+    pub authority: Pubkey,       // Authority allowed to deregister the oracle
+    pub g2_point_key: [u8; 128], // Compressed G2 public key stored as raw bytes
+    pub bump: u8,
+    pub committee: Option<Pubkey>, // Committee the oracle has joined, if any
+    pub bonded: u64,             // Tokens bonded in the vault and counted as weight
+    pub unbonding: Vec<UnbondingRequest>, // Tokens waiting out the unbonding delay
+    pub jailed: bool,            // Set when slashed; jailed oracles cannot sign, join committees, unbond or deregister
+    pub delegated: u64,          // Tokens delegated to the oracle, counted as weight
+    pub commission_bps: u16,     // Share of the delegators' rewards kept by the operator
+    pub operator_rewards: u64,   // Rewards owed to the authority, held in the reward pool
+    pub reward_per_share: u128,  // Delegator rewards per delegated token, scaled by REWARD_SCALE
+    pub weight_checkpoints: Vec<WeightCheckpoint>, // Weight at recent committee epochs
+    pub pending_delegated: u64,  // Delegated tokens no checkpoint counts yet, which earn nothing
+    pub pending_delegations: u32, // Delegations holding those tokens
+    pub accepts_delegations: bool, // Whether the operator accepts new delegations
+}
+
+impl OracleAccount {
+    pub const LEN: usize = 32 + 128 + 1 + (1 + 32) + 8 + 4 + MAX_UNBONDING_REQUESTS * UnbondingRequest::LEN + 1
+        + 8 + 2 + 8 + 16 + 4 + MAX_WEIGHT_CHECKPOINTS * WeightCheckpoint::LEN + 8 + 4 + 1;
+
+    /// Record `weight`, or the oracle's current weight (its bond plus delegations) if none is
+    /// given, for committee `epoch`. A given weight cannot exceed the current one. Delegations
+    /// pending until then start earning. At most `MAX_WEIGHT_CHECKPOINTS` epochs are kept, see
+    /// `evict_checkpoint`. Returns the weight.
+    pub fn checkpoint_weight(&mut self, epoch: u64, weight: Option<u64>) -> Result<u64> {
+        let stake = self
+            .bonded
+            .checked_add(self.delegated)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        let weight = weight.unwrap_or(stake);
+        require!(weight <= stake, ErrorCode::WeightAboveStake);
+        let advanced = match self.weight_checkpoints.last() {
+            Some(latest) => epoch > latest.epoch,
+            None => true,
+        };
+        let mut pending_delegations = 0;
+        if advanced {
+            self.pending_delegated = 0;
+            pending_delegations = std::mem::take(&mut self.pending_delegations);
+        }
+        if let Some(index) = self.weight_checkpoints.iter().position(|checkpoint| checkpoint.epoch == epoch) {
+            pending_delegations += self.weight_checkpoints.remove(index).pending_delegations;
+        }
+        if self.weight_checkpoints.len() == MAX_WEIGHT_CHECKPOINTS {
+            self.evict_checkpoint();
+        }
+        let reward_per_share = self.reward_per_share;
+        self.weight_checkpoints.push(WeightCheckpoint { epoch, weight, reward_per_share, pending_delegations });
+        Ok(weight)
+    }
+
+    /// Drop the oldest checkpoint no unsettled delegation starts earning at, so those delegations
+    /// keep the rewards earned since theirs. If every checkpoint has some, drop the oldest one:
+    /// its delegations then start earning at the next kept checkpoint and lose what was earned
+    /// in between.
+    fn evict_checkpoint(&mut self) {
+        match self.weight_checkpoints.iter().position(|checkpoint| checkpoint.pending_delegations == 0) {
+            Some(index) => {
+                self.weight_checkpoints.remove(index);
+            }
+            None => {
+                let oldest = self.weight_checkpoints.remove(0);
+                if let Some(next) = self.weight_checkpoints.first_mut() {
+                    next.pending_delegations = next.pending_delegations.saturating_add(oldest.pending_delegations);
+                }
+            }
+        }
+    }
+
+    /// The earliest kept checkpoint at or after committee `epoch`.
+    pub fn checkpoint_since(&self, epoch: u64) -> Option<&WeightCheckpoint> {
+        self.weight_checkpoints.iter().find(|checkpoint| checkpoint.epoch >= epoch)
+    }
+
+    /// Split `amount` lamports of rewards between the operator and the delegators by the weight
+    /// checkpointed for the oracle's latest epoch, counting only delegations that checkpoint
+    /// covers and that are still delegated. The operator keeps its commission on the delegators'
+    /// part and everything when no checkpoint was taken.
+    pub fn accrue_rewards(&mut self, amount: u64) -> Result<()> {
+        let mut to_operator = amount;
+        let earning = self.delegated - self.pending_delegated;
+        if let Some(checkpoint) = self.weight_checkpoints.last().filter(|_| amount > 0 && earning > 0) {
+            let total = u128::from(checkpoint.weight).max(u128::from(earning));
+            let gross = u128::from(amount) * u128::from(earning) / total;
+            let to_delegators = gross - gross * u128::from(self.commission_bps) / 10_000;
+            self.reward_per_share = to_delegators
+                .checked_mul(REWARD_SCALE)
+                .map(|scaled| scaled / u128::from(earning))
+                .and_then(|increase| self.reward_per_share.checked_add(increase))
+                .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+            to_operator -= to_delegators as u64;
+        }
+        self.operator_rewards = self
+            .operator_rewards
+            .checked_add(to_operator)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        Ok(())
+    }
+
+    /// Remove `slash_bps` of the bonded and unbonding tokens, taking from the bond first and then
+    /// from the oldest unbond requests. Returns the amount slashed.
+    pub fn slash(&mut self, slash_bps: u16) -> Result<u64> {
+        let unbonding = self
+            .unbonding
+            .iter()
+            .try_fold(0u64, |total, request| total.checked_add(request.amount))
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        let total = u128::from(self.bonded) + u128::from(unbonding);
+        let amount = (total * u128::from(slash_bps) / 10_000) as u64;
+
+        let mut remaining = amount;
+        let from_bond = remaining.min(self.bonded);
+        self.bonded -= from_bond;
+        remaining -= from_bond;
+        for request in self.unbonding.iter_mut() {
+            let taken = remaining.min(request.amount);
+            request.amount -= taken;
+            remaining -= taken;
+        }
+        self.unbonding.retain(|request| request.amount > 0);
+        Ok(amount)
+    }
+
+    /// Derive the oracle PDA for `authority` in the registry identified by `config`.
+    pub fn find_address(config: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ORACLE_SEED, config.as_ref(), authority.as_ref()], &crate::ID)
+    }
+}
+
+/// A committee member as hashed into a Merkle leaf.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct WeightedKey {
+    pub g2_point_key: [u8; 128], // Member's G2 public key
+    pub weight: u64,             // Member's voting weight
+}
+
+/// The participating keys of a Merkle committee together with their multiproof.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MerkleSignerProof {
+    pub signers: Vec<WeightedKey>, // Participating members, in tree order
+    pub proof: Vec<[u8; 32]>,      // Sibling nodes not derivable from the signers
+    pub proof_flags: Vec<bool>,    // See `process_multiproof`
+}
+
+/// Data structure for a committee stored as a Merkle root over weighted keys.
+#[account]
+pub struct MerkleCommittee {
+    pub config: Pubkey,      // Registry the committee belongs to
+    pub committee_id: u64,   // Identifier within the registry
+    pub root: [u8; 32],      // Merkle root over `merkle_leaf(key, weight)` of every member
+    pub member_count: u32,   // Number of leaves in the tree
+    pub total_weight: u64,   // Sum of every member's weight
+    pub quorum_weight: u64,  // Minimum signing weight
+    pub bump: u8,
+}
+
+impl MerkleCommittee {
+    pub const LEN: usize = 32 + 8 + 32 + 4 + 8 + 8 + 1;
+
+    /// Check the signers against the root and quorum, returning their total weight.
+    pub fn verify_signer_proof(&self, signer_proof: &MerkleSignerProof) -> Result<u64> {
+        require!(
+            signer_proof.signers.len() <= self.member_count as usize,
+            ErrorCode::InvalidMerkleProof
+        );
+        let mut leaves: Vec<[u8; 32]> = Vec::with_capacity(signer_proof.signers.len());
+        let mut signing_weight: u64 = 0;
+        for signer in &signer_proof.signers {
+            let leaf = merkle_leaf(&signer.g2_point_key, signer.weight);
+            require!(!leaves.contains(&leaf), ErrorCode::DuplicateOracle);
+            leaves.push(leaf);
+            signing_weight = signing_weight
+                .checked_add(signer.weight)
+                .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        }
+
+        let root = process_multiproof(&leaves, &signer_proof.proof, &signer_proof.proof_flags)?;
+        require!(root == self.root, ErrorCode::InvalidMerkleProof);
+        require!(signing_weight >= self.quorum_weight, ErrorCode::ThresholdNotMet);
+        Ok(signing_weight)
+    }
+}
+
+/// Data structure for a buffer holding a serialized `MerkleSignerProof`. The raw bytes follow
+/// the header at `DATA_OFFSET`.
+#[account]
+pub struct ProofBuffer {
+    pub authority: Pubkey, // Owner allowed to write and close the buffer
+    pub capacity: u32,     // Bytes available after the header
+    pub len: u32,          // Bytes written so far
+    pub bump: u8,
+}
+
+impl ProofBuffer {
+    pub const LEN: usize = 32 + 4 + 4 + 1;
+    pub const DATA_OFFSET: usize = 8 + Self::LEN;
+
+    /// Deserialize the `MerkleSignerProof` written into the buffer.
+    pub fn load_signer_proof(proof_buffer: &Account<ProofBuffer>) -> Result<MerkleSignerProof> {
+        let buffer_info = proof_buffer.to_account_info();
+        let data = buffer_info.try_borrow_data()?;
+        let bytes = &data[Self::DATA_OFFSET..Self::DATA_OFFSET + proof_buffer.len as usize];
+        MerkleSignerProof::try_from_slice(bytes).map_err(|_| error!(ErrorCode::InvalidMerkleProof))
+    }
+}
+
+/// Data structure for the header of a registry whose members live in zero-copy pages.
+#[account]
+pub struct PagedRegistry {
+    pub config: Pubkey,     // Program config the registry belongs to
+    pub registry_id: u64,   // Identifier within the program config
+    pub page_count: u32,    // Number of pages created
+    pub member_count: u32,  // Number of active members across all pages
+    pub total_weight: u64,  // Sum of the active members' weights
+    pub quorum_weight: u64, // Minimum signing weight
+    pub version: u64,       // Incremented on every membership change
+    pub bump: u8,
+}
+
+impl PagedRegistry {
+    pub const LEN: usize = 32 + 8 + 4 + 4 + 8 + 8 + 8 + 1;
+}
+
+/// A member slot in a registry page.
+#[zero_copy]
+pub struct RegistryEntry {
+    pub g2_point_key: [u8; 128], // Member's G2 public key
+    pub weight: u64,             // Member's voting weight
+    pub status: u8,              // `EMPTY`, `ACTIVE` or `REMOVED`
+    pub _padding: [u8; 7],
+}
+
+impl RegistryEntry {
+    pub const EMPTY: u8 = 0;
+    pub const ACTIVE: u8 = 1;
+    pub const REMOVED: u8 = 2;
+
+    /// An empty slot.
+    pub fn zeroed() -> Self {
+        Self { g2_point_key: [0; 128], weight: 0, status: Self::EMPTY, _padding: [0; 7] }
+    }
+}
+
+/// Data structure for a fixed-size page of registry members.
+#[account(zero_copy)]
+pub struct RegistryPage {
+    pub registry: Pubkey,  // Registry the page belongs to
+    pub page_index: u32,   // Position of the page in the registry
+    pub len: u32,          // Slots in use, active or removed
+    pub _padding: [u8; 8],
+    pub entries: [RegistryEntry; PAGE_CAPACITY], // Member slots
+}
+
+/// Data structure for a verification whose signers are aggregated over several transactions.
+#[account]
+pub struct VerificationSession {
+    pub owner: Pubkey,                // Only signer allowed to continue or close the session
+    pub registry: Pubkey,             // Registry the signers belong to
+    pub registry_version: u64,        // Registry version the session started at
+    pub message_hash: [u8; 32],       // SHA-256 of the signed message
+    pub signature: [u8; 32],          // Aggregated and compressed G1 signature
+    pub next_index: u32,              // Cursor: every later signer index must be at least this
+    pub signer_count: u32,            // Signers aggregated so far
+    pub signing_weight: u64,          // Weight of the signers aggregated so far
+    pub partial_aggregate: [u8; 128], // Sum of the G2 keys aggregated so far
+    pub verified: bool,               // Set once the pairing check has passed
+    pub bump: u8,
+}
+
+impl VerificationSession {
+    pub const LEN: usize = 32 + 32 + 8 + 32 + 32 + 4 + 4 + 8 + 128 + 1 + 1;
+}
+
+/// A price observation signed by a committee. Committees sign `price_report_message` over its
+/// Borsh encoding.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PriceReport {
+    pub feed_id: [u8; 32],  // Feed the report is for
+    pub price: i64,         // Price as a fixed-point integer
+    pub expo: i32,          // Price is `price * 10^expo`
+    pub conf: u64,          // Confidence interval, in the same units as `price`
+    pub publish_time: i64,  // Unix timestamp the price was observed at
+    pub round_id: u64,      // Monotonic round number
+}
+
+impl PriceReport {
+    pub const LEN: usize = 32 + 8 + 4 + 8 + 8 + 8;
+
+    /// Whether this report comes after `other`, by publish time and then by round.
+    pub fn is_newer_than(&self, other: &PriceReport) -> bool {
+        (self.publish_time, self.round_id) > (other.publish_time, other.round_id)
+    }
+
+    /// Whether this report gives a different price than `other` for the same feed and round.
+    /// Confidence and publish time are left out, since honest signers may differ on them.
+    pub fn contradicts(&self, other: &PriceReport) -> bool {
+        self.feed_id == other.feed_id
+            && self.round_id == other.round_id
+            && (self.price, self.expo) != (other.price, other.expo)
+    }
+}
+
+/// A Borsh-encoded `PriceReport` with the committee signature over its `price_report_message`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CommitteeSignedReport {
+    pub epoch: u64,                                   // Epoch of the committee that signed
+    pub signer_bitmap: u32,                           // Members that signed
+    pub aggregated_and_compressed_g1_signature: [u8; 32], // Aggregated G1 signature
+    pub report: Vec<u8>,                              // Borsh-encoded `PriceReport`
+}
+
+/// A committee-signed payload with the time it was produced. Committees sign
+/// `timestamped_message` over its Borsh encoding.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TimestampedMessage {
+    pub timestamp: i64,    // Unix timestamp the payload was produced at
+    pub payload: Vec<u8>,  // Application data
+}
+
+/// Update rules for a price feed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct FeedSettings {
+    pub min_signers: u16,     // Signers required on top of the committee threshold, 0 for none
+    pub expo: i32,            // Exponent every report must use
+    pub max_staleness: i64,   // Oldest accepted report age in seconds, 0 to disable
+    pub max_future_skew: i64, // Furthest accepted report time ahead of the clock, 0 to disable
+    pub deviation_bps: u16,   // Price move in basis points that triggers an update, 0 to disable
+    pub heartbeat_secs: i64,  // Seconds after which an update is accepted regardless, 0 to disable
+}
+
+impl FeedSettings {
+    pub const LEN: usize = 2 + 4 + 8 + 8 + 2 + 8;
+
+    /// Reject negative time limits and quorums no committee can reach.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_staleness >= 0 && self.max_future_skew >= 0 && self.heartbeat_secs >= 0,
+            ErrorCode::InvalidTimestampLimits
+        );
+        require!(self.min_signers as usize <= MAX_COMMITTEE_MEMBERS, ErrorCode::InvalidThreshold);
+        Ok(())
+    }
+}
+
+/// A feed's entry in the feed registry.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeedPolicy {
+    pub feed_id: [u8; 32],      // Feed the policy applies to
+    pub committee: Pubkey,      // Committee allowed to update the feed
+    pub settings: FeedSettings, // Update rules
+    pub retired: bool,          // Set once the feed no longer accepts updates
+    pub frozen: bool,           // Set after equivocation; frozen feeds reject updates and reads
+    pub frozen_slot: u64,       // Slot of the latest freeze, 0 if never frozen
+}
+
+impl FeedPolicy {
+    pub const LEN: usize = 32 + 32 + FeedSettings::LEN + 1 + 1 + 8;
+
+    /// Check a single signed report against the committee's and the feed's time limits, the
+    /// feed id and the exponent.
+    pub fn check_report(&self, committee: &Committee, report: &PriceReport, now: i64) -> Result<()> {
+        let settings = &self.settings;
+        check_timestamp(report.publish_time, now, committee.max_staleness, committee.max_future_skew)?;
+        check_timestamp(report.publish_time, now, settings.max_staleness, settings.max_future_skew)?;
+        require!(report.feed_id == self.feed_id, ErrorCode::FeedMismatch);
+        require!(report.expo == settings.expo, ErrorCode::InvalidReport);
+        Ok(())
+    }
+
+    /// Check that `report` may replace `latest`: it must be newer, signed by enough members, and
+    /// either deviate enough from the stored price or arrive after the heartbeat. The first report
+    /// and feeds without a deviation or heartbeat policy skip the last check.
+    pub fn check_update(&self, latest: &PriceReport, report: &PriceReport, signer_count: u16) -> Result<()> {
+        let settings = &self.settings;
+        require!(report.is_newer_than(latest), ErrorCode::StaleReport);
+        require!(signer_count >= settings.min_signers, ErrorCode::ThresholdNotMet);
+        if latest.round_id == 0 || (settings.deviation_bps == 0 && settings.heartbeat_secs == 0) {
+            return Ok(());
+        }
+        let heartbeat_elapsed = settings.heartbeat_secs > 0
+            && report.publish_time.saturating_sub(latest.publish_time) >= settings.heartbeat_secs;
+        let deviated = settings.deviation_bps > 0 && {
+            let old = i128::from(latest.price);
+            let moved = (i128::from(report.price) - old).abs();
+            moved * 10_000 >= i128::from(settings.deviation_bps) * old.abs() && moved > 0
+        };
+        require!(heartbeat_elapsed || deviated, ErrorCode::UpdateNotNeeded);
+        Ok(())
+    }
+}
+
+/// Data structure for the registry mapping each feed id to its committee and update rules.
+#[account]
+pub struct FeedRegistry {
+    pub config: Pubkey,          // Program config the registry belongs to
+    pub feeds: Vec<FeedPolicy>,  // Policies of every feed, retired ones included
+    pub bump: u8,
+}
+
+impl FeedRegistry {
+    pub const LEN: usize = 32 + 4 + MAX_FEEDS * FeedPolicy::LEN + 1;
+
+    /// The policy for `feed_id`, whether or not it is retired or frozen.
+    pub fn find_policy(&self, feed_id: &[u8; 32]) -> Result<&FeedPolicy> {
+        self.feeds
+            .iter()
+            .find(|policy| policy.feed_id == *feed_id)
+            .ok_or_else(|| error!(ErrorCode::UnknownFeed))
+    }
+
+    /// The live, unfrozen policy for `feed_id`.
+    pub fn active_policy(&self, feed_id: &[u8; 32]) -> Result<&FeedPolicy> {
+        let policy = self.find_policy(feed_id)?;
+        require!(!policy.retired, ErrorCode::FeedRetired);
+        require!(!policy.frozen, ErrorCode::FeedFrozen);
+        Ok(policy)
+    }
+
+    /// The live, unfrozen policy for `feed_id`, provided `committee` is the one allowed to update it.
+    pub fn policy(&self, feed_id: &[u8; 32], committee: &Pubkey) -> Result<&FeedPolicy> {
+        let policy = self.active_policy(feed_id)?;
+        require_keys_eq!(policy.committee, *committee, ErrorCode::CommitteeMismatch);
+        Ok(policy)
+    }
+
+    /// Add the policy of a new feed.
+    pub fn register(&mut self, policy: FeedPolicy) -> Result<()> {
+        require!(self.feeds.len() < MAX_FEEDS, ErrorCode::FeedRegistryFull);
+        require!(
+            self.feeds.iter().all(|existing| existing.feed_id != policy.feed_id),
+            ErrorCode::FeedExists
+        );
+        self.feeds.push(policy);
+        Ok(())
+    }
+
+    /// The policy for `feed_id`, retired or not.
+    pub fn policy_mut(&mut self, feed_id: &[u8; 32]) -> Result<&mut FeedPolicy> {
+        self.feeds
+            .iter_mut()
+            .find(|policy| policy.feed_id == *feed_id)
+            .ok_or_else(|| error!(ErrorCode::UnknownFeed))
+    }
+}
+
+/// Data structure for a price feed. Its committee and update rules live in the `FeedRegistry`.
+#[account]
+pub struct PriceFeed {
+    pub config: Pubkey,         // Program config the feed belongs to
+    pub feed_id: [u8; 32],      // Identifier signed into every report
+    pub latest: PriceReport,    // Most recent verified report
+    pub last_update_slot: u64,  // Slot the latest report was stored
+    pub min_price: i64,         // Lowest price observed in the latest update
+    pub max_price: i64,         // Highest price observed in the latest update
+    pub signer_count: u16,      // Members that signed the latest update
+    pub optimistic: PriceReport, // Most recent finalized optimistic report
+    pub optimistic_update_slot: u64, // Slot the optimistic report was finalized
+    pub bump: u8,
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 32 + 32 + PriceReport::LEN + 8 + 8 + 8 + 2 + PriceReport::LEN + 8 + 1;
+
+    /// The latest report, failing if it was published more than `max_age` seconds before `now`.
+    pub fn get_price_no_older_than(&self, now: i64, max_age: i64) -> Result<PriceReport> {
+        require!(self.latest.round_id > 0, ErrorCode::StalePrice); // Never updated
+        require!(self.latest.publish_time >= now.saturating_sub(max_age), ErrorCode::StalePrice);
+        Ok(self.latest)
+    }
+
+    /// The latest report, failing if the feed is retired or frozen or the report is older than
+    /// the registered `max_staleness`.
+    pub fn get_price(&self, policy: &FeedPolicy, clock: &Clock) -> Result<PriceReport> {
+        require!(policy.feed_id == self.feed_id, ErrorCode::FeedMismatch);
+        require!(!policy.retired, ErrorCode::FeedRetired);
+        require!(!policy.frozen, ErrorCode::FeedFrozen);
+        let max_staleness = policy.settings.max_staleness;
+        let max_age = if max_staleness > 0 { max_staleness } else { i64::MAX };
+        self.get_price_no_older_than(clock.unix_timestamp, max_age)
+    }
+
+    /// Like `get_price`, but returns the finalized optimistic report when it is newer than the
+    /// verified one. Optimistic reports are only as trustworthy as the dispute window is watched.
+    pub fn get_optimistic_price(&self, policy: &FeedPolicy, clock: &Clock) -> Result<PriceReport> {
+        if !self.optimistic.is_newer_than(&self.latest) {
+            return self.get_price(policy, clock);
+        }
+        let feed = PriceFeed { latest: self.optimistic, ..self.clone() };
+        feed.get_price(policy, clock)
+    }
+}
+
+/// A verified update recorded in a feed's history.
+#[zero_copy]
+pub struct HistoryEntry {
+    pub price: i64,     // Stored price
+    pub conf: u64,      // Stored confidence
+    pub slot: u64,      // Slot the update landed in
+    pub timestamp: i64, // Publish time of the update
+}
+
+/// Data structure for a ring buffer of a feed's most recent updates.
+#[account(zero_copy)]
+pub struct PriceHistory {
+    pub feed: Pubkey, // Feed the history belongs to
+    pub head: u32,    // Slot the next update is written to
+    pub len: u32,     // Entries in use, at most `HISTORY_CAPACITY`
+    pub entries: [HistoryEntry; HISTORY_CAPACITY], // Updates, oldest overwritten first
+}
+
+impl PriceHistory {
+    /// Record an update, overwriting the oldest entry once the buffer is full.
+    pub fn push(&mut self, report: &PriceReport, slot: u64) {
+        self.entries[self.head as usize] = HistoryEntry {
+            price: report.price,
+            conf: report.conf,
+            slot,
+            timestamp: report.publish_time,
+        };
+        self.head = ((self.head as usize + 1) % HISTORY_CAPACITY) as u32;
+        self.len = (self.len + 1).min(HISTORY_CAPACITY as u32);
+    }
+
+    /// Entries from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        let oldest = (self.head as usize + HISTORY_CAPACITY - self.len as usize) % HISTORY_CAPACITY;
+        (0..self.len as usize).map(move |offset| &self.entries[(oldest + offset) % HISTORY_CAPACITY])
+    }
+
+    /// Time-weighted average price over `[now - window, now]`, each price weighted by how long
+    /// it was the latest. Fails if the history does not reach back to the start of the window.
+    pub fn twap(&self, now: i64, window: i64) -> Result<i64> {
+        require!(window > 0, ErrorCode::InsufficientHistory);
+        let start = now.checked_sub(window).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        let mut end = now;
+        let mut weighted: i128 = 0;
+        for entry in self.iter().rev() {
+            let from = entry.timestamp.max(start);
+            if end > from {
+                weighted += i128::from(entry.price) * i128::from(end - from);
+                end = from;
+            }
+            if entry.timestamp <= start {
+                return Ok((weighted / i128::from(window)) as i64);
+            }
+        }
+        err!(ErrorCode::InsufficientHistory)
+    }
+}
+
+/// Data structure for a Merkle root over price reports, attested by a committee.
+#[account]
+pub struct AttestedRoot {
+    pub committee: Pubkey,   // Committee that signed the root
+    pub root: [u8; 32],      // Root of the report tree
+    pub epoch: u64,          // Committee epoch that signed
+    pub signer_count: u16,   // Members that signed
+    pub attested_slot: u64,  // Slot the root was attested
+    pub rent_payer: Pubkey,  // Refunded when the root is closed
+    pub bump: u8,
+}
+
+impl AttestedRoot {
+    pub const LEN: usize = 32 + 32 + 8 + 2 + 8 + 32 + 1;
+}
+
+/// Rewards owed to one oracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RewardBalance {
+    pub oracle: Pubkey, // Oracle PDA
+    pub owed: u64,      // Lamports the oracle can claim
+}
+
+impl RewardBalance {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Data structure for the pool paying oracles for verified updates. Its lamports cover rent,
+/// `available` and every balance.
+#[account]
+pub struct RewardPool {
+    pub config: Pubkey,                 // Program config the pool belongs to
+    pub fee_per_update: u64,            // Lamports paid out per verified update
+    pub available: u64,                 // Funded lamports not yet credited to oracles
+    pub balances: Vec<RewardBalance>,   // Unclaimed rewards per oracle
+    pub bump: u8,
+}
+
+impl RewardPool {
+    pub const LEN: usize = 32 + 8 + 8 + 4 + MAX_REWARD_BALANCES * RewardBalance::LEN + 1;
+
+    /// Split the per-update fee evenly among the members of `set` selected by `signer_bitmap`,
+    /// as far as the funded balance allows. The remainder of the split stays available. Signers
+    /// without a balance slot once the pool is full are skipped and their share stays available.
+    pub fn credit(&mut self, set: &CommitteeSet, signer_bitmap: u32) -> Result<()> {
+        let signers = signer_bitmap.count_ones() as u64;
+        let fee = self.fee_per_update.min(self.available);
+        if signers == 0 || fee < signers {
+            return Ok(());
+        }
+        let share = fee / signers;
+        for (index, member) in set.members.iter().enumerate() {
+            if signer_bitmap & (1 << index) == 0 {
+                continue;
+            }
+            if let Some(balance) = self.balances.iter_mut().find(|balance| balance.oracle == member.oracle) {
+                balance.owed = balance.owed.checked_add(share).ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+            } else if self.balances.len() < MAX_REWARD_BALANCES {
+                self.balances.push(RewardBalance { oracle: member.oracle, owed: share });
+            } else {
+                msg!("Reward pool full, skipping oracle {}", member.oracle);
+                continue;
+            }
+            self.available -= share;
+        }
+        Ok(())
+    }
+
+    /// Remove and return the balance owed to `oracle`.
+    pub fn take_balance(&mut self, oracle: &Pubkey) -> u64 {
+        match self.balances.iter().position(|balance| balance.oracle == *oracle) {
+            Some(position) => self.balances.swap_remove(position).owed,
+            None => 0,
+        }
+    }
+}
+
+/// Data structure for tokens a holder has delegated to an oracle.
+#[account]
+pub struct Delegation {
+    pub oracle: Pubkey,                   // Oracle delegated to
+    pub delegator: Pubkey,                // Owner of the delegation
+    pub amount: u64,                      // Tokens delegated and earning rewards
+    pub reward_debt: u128,                // Rewards per share already accounted for, times amount
+    pub unbonding: Vec<UnbondingRequest>, // Tokens waiting out the unbonding delay
+    pub bump: u8,
+    pub pending: u64,                     // Tokens delegated but not yet counted by a checkpoint
+    pub pending_epoch: u64,               // First checkpoint epoch that counts the pending tokens
+}
+
+impl Delegation {
+    pub const LEN: usize = 32 + 32 + 8 + 16 + 4 + MAX_UNBONDING_REQUESTS * UnbondingRequest::LEN + 1 + 8 + 8;
+
+    /// Once `oracle` has checkpointed an epoch counting the pending tokens, add them to the
+    /// earning amount. Returns what they earned since that checkpoint; the caller resets the
+    /// reward debt.
+    pub fn activate_pending(&mut self, oracle: &mut OracleAccount) -> Result<u64> {
+        if self.pending == 0 {
+            return Ok(0);
+        }
+        let reward_per_share = oracle.reward_per_share;
+        let pending_epoch = self.pending_epoch;
+        let Some(checkpoint) = oracle.weight_checkpoints.iter_mut().find(|checkpoint| checkpoint.epoch >= pending_epoch)
+        else {
+            return Ok(0);
+        };
+        checkpoint.pending_delegations = checkpoint.pending_delegations.saturating_sub(1);
+        let earned = u128::from(self.pending)
+            .checked_mul(reward_per_share.saturating_sub(checkpoint.reward_per_share))
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?
+            / REWARD_SCALE;
+        self.amount = self
+            .amount
+            .checked_add(self.pending)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?;
+        self.pending = 0;
+        Ok(earned as u64)
+    }
+
+    /// Rewards earned since the reward debt was last reset.
+    pub fn pending_rewards(&self, reward_per_share: u128) -> Result<u64> {
+        let earned = u128::from(self.amount)
+            .checked_mul(reward_per_share)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?
+            / REWARD_SCALE;
+        Ok(earned.saturating_sub(self.reward_debt) as u64)
+    }
+
+    /// Mark every reward up to `reward_per_share` as paid for the current amount.
+    pub fn reset_reward_debt(&mut self, reward_per_share: u128) -> Result<()> {
+        self.reward_debt = u128::from(self.amount)
+            .checked_mul(reward_per_share)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))?
+            / REWARD_SCALE;
+        Ok(())
+    }
+}
+
+/// Data structure for an optimistic report waiting out its dispute window.
+#[account]
+pub struct OptimisticReport {
+    pub feed: Pubkey,        // Feed the report is for
+    pub poster: Pubkey,      // Relayer that posted it
+    pub report: PriceReport, // Claimed report
+    pub bond: u64,           // Lamports forfeited if disputed
+    pub final_slot: u64,     // First slot the report can be finalized
+    pub bump: u8,
+}
+
+impl OptimisticReport {
+    pub const LEN: usize = 32 + 32 + PriceReport::LEN + 8 + 8 + 1;
+}
+
+/// A change the governance committee can authorize.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GovernanceAction {
+    SetPaused { paused: bool },                                                   // Pause or unpause the program
+    SetWeightQuorum { committee: Pubkey, quorum_bps: u16 },                       // Change a committee's weight quorum
+    UnfreezeCommittee { committee: Pubkey },                                      // Unfreeze a committee after review
+    AddFeed { feed_id: [u8; 32], committee: Pubkey, settings: FeedSettings },     // Register a new feed
+    UpdateFeedPolicy { feed_id: [u8; 32], committee: Pubkey, settings: FeedSettings }, // Replace a feed's policy
+    RetireFeed { feed_id: [u8; 32] },                                             // Retire a feed
+    UnfreezeFeed { feed_id: [u8; 32] },                                           // Unfreeze a feed after review
+    SetGovernanceCommittee { committee: Pubkey },                                 // Hand governance to another committee
+    UpdateConfig { registration_mode: RegistrationMode, max_committee_size: u16 }, // Change registration and committee size
+    ProposeAdmin { new_admin: Pubkey },                                           // Nominate a new admin
+    SetBondParameters { min_bond: u64, unbonding_delay_slots: u64 },              // Change the bond requirements
+    SetOptimisticParameters { optimistic_bond: u64, dispute_window_slots: u64 },  // Change optimistic report terms
+    SetHandoffWindow { committee: Pubkey, handoff_window_slots: u64 },            // Change a committee's handoff window
+    SetTimestampLimits { committee: Pubkey, max_staleness: i64, max_future_skew: i64 }, // Change a committee's timestamp limits
+    SetSnapshotRetention { committee: Pubkey, snapshot_retention_epochs: u64 },   // Change a committee's snapshot retention
+}
+
+/// A governance action with its replay protection. Committees sign `governance_message` over it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GovernanceProposal {
+    pub nonce: u64,               // Must equal the config's governance nonce
+    pub expiry_slot: u64,         // Last slot the proposal can execute in
+    pub action: GovernanceAction, // Change to apply
+}
+
+/// A transfer out of a committee vault.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TransferIntent {
+    pub destination: Pubkey,  // Receiving account, or token account for SPL transfers
+    pub mint: Option<Pubkey>, // Token mint, or `None` for SOL
+    pub amount: u64,          // Lamports or token base units
+    pub nonce: u64,           // Must equal the vault's nonce
+    pub expiry_slot: u64,     // Last slot the transfer can execute in
+}
+
+/// Data structure for a vault whose spending authority is a committee's signature.
+#[account]
+pub struct CommitteeVault {
+    pub committee: Pubkey, // Committee that authorizes transfers
+    pub nonce: u64,        // Nonce the next transfer intent must carry
+    pub bump: u8,
+}
+
+impl CommitteeVault {
+    pub const LEN: usize = 32 + 8 + 1;
+}
+
+/// A deposit on a bridge's source chain, as attested by its committee.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DepositAttestation {
+    pub source_chain_id: u64, // Chain the deposit was made on
+    pub tx_hash: [u8; 32],    // Hash of the deposit transaction
+    pub recipient: Pubkey,    // Owner of the token account credited on Solana
+    pub amount: u64,          // Wrapped token base units to mint
+    pub nonce: u64,           // Deposit nonce on the source chain
+}
+
+/// Data structure for a bridge from another chain, minting a wrapped token.
+#[account]
+pub struct Bridge {
+    pub committee: Pubkey,     // Committee attesting deposits
+    pub source_chain_id: u64,  // Chain deposits come from and burns are released to
+    pub mint: Pubkey,          // Wrapped token mint, minted by this PDA
+    pub outbound_nonce: u64,   // Nonce of the next burn
+    pub bump: u8,
+}
+
+impl Bridge {
+    pub const LEN: usize = 32 + 8 + 32 + 8 + 1;
+}
+
+/// Data structure marking a bridged deposit as claimed.
+#[account]
+pub struct ConsumedDeposit {
+    pub bridge: Pubkey,     // Bridge the deposit was claimed through
+    pub tx_hash: [u8; 32],  // Hash of the deposit transaction
+    pub nonce: u64,         // Deposit nonce on the source chain
+    pub claimed_slot: u64,  // Slot the deposit was claimed
+    pub bump: u8,
+}
+
+impl ConsumedDeposit {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 1;
+}
@@ -1,5 +1,5 @@
 use solana_alt_bn128_bls::{G1CompressedPoint, G2CompressedPoint, G1Point, G2Point};
-use anyhow::{Result, Context};
+use anyhow::Result;
 
 /// A struct for BLS utility functions.
 pub struct BlsUtils;
@@ -11,9 +11,9 @@ impl BlsUtils {
             return Err(anyhow::anyhow!("No G1 points provided for aggregation"));
         }
 
-        let mut aggregated = points[0].clone();
+        let mut aggregated = points[0];
         for point in points.iter().skip(1) {
-            aggregated = aggregated + point.clone();
+            aggregated = aggregated + *point;
         }
         Ok(aggregated)
     }
@@ -24,9 +24,9 @@ impl BlsUtils {
             return Err(anyhow::anyhow!("No G2 points provided for aggregation"));
         }
 
-        let mut aggregated = points[0].clone();
+        let mut aggregated = points[0];
         for point in points.iter().skip(1) {
-            aggregated = aggregated + point.clone();
+            aggregated = aggregated + *point;
         }
         Ok(aggregated)
     }
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_lang::InstructionData;
-use solana_alt_bn128_bls::{G1CompressedPoint, G2CompressedPoint, G1Point, G2Point, PrivKey, Sha256Normalized};
+use solana_alt_bn128_bls::{G1CompressedPoint, G1Point, G2Point, PrivKey, Sha256Normalized};
 // use solana_program_test::*;
 use solana_sdk::{
+    system_instruction,
     transaction::Transaction,
 };
 use std::convert::TryFrom;
@@ -15,13 +16,30 @@ use anchor_client::{
     },
     Client, Cluster,
 };
-use std::rc::Rc;
-use latest_bls::{accounts, instruction};
-use solana_sdk::instruction::Instruction;
-use solana_sdk::commitment_config::CommitmentConfig;
+use latest_bls::{application_message, proof_of_possession_message, OracleAccount, CONFIG_SEED, ORACLE_SEED};
+use solana_sdk::instruction::{AccountMeta, Instruction};
 use dirs::home_dir;
 
 
+/// Aggregate and compress the G1 signatures of `private_keys` over `message`.
+fn sign_aggregate(private_keys: &[PrivKey], message: &[u8]) -> [u8; 32] {
+    let g1_signatures: Vec<G1Point> = private_keys
+        .iter()
+        .map(|key| {
+            key.sign::<Sha256Normalized, &[u8]>(message)
+                .expect("Failed to sign message with private key")
+        })
+        .collect();
+
+    let mut aggregated_g1_signature = g1_signatures[0].clone();
+    for g1_signature in g1_signatures.iter().skip(1) {
+        aggregated_g1_signature = aggregated_g1_signature + g1_signature.clone();
+    }
+
+    G1CompressedPoint::try_from(aggregated_g1_signature).expect("Failed to compress G1 signature").0
+}
+
+// Expects the program configuration to be initialized in open registration mode.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_full_verification_on_chain() {
    let keypair_path = home_dir()
@@ -38,56 +56,70 @@ let payer = read_keypair_file(keypair_path)
    );
    let program_id = Pubkey::from_str("E4LXwvzGcZzdJfURbdLDW2BLqz73JLq9ncBd6NteYik").expect("Failed to parse program ID");
    let program = client.program(program_id).expect( "Failed to fetch program");
-   
+   let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+
 
-   // Step 2: Generate private keys and derive G2 points
+   // Step 2: Generate private keys and fresh authorities for the oracles
    let private_keys: Vec<PrivKey> = (0..5)
        .map(|_| PrivKey::from_random())
        .collect();
+   let authorities: Vec<Keypair> = (0..5).map(|_| Keypair::new()).collect();
+   let rent = program
+       .rpc()
+       .get_minimum_balance_for_rent_exemption(8 + OracleAccount::LEN)
+       .expect("Failed to fetch rent");
 
-   let g2_points: Vec<G2Point> = private_keys
-       .iter()
-       .map(|key| G2Point::try_from(key).expect("Failed to derive G2 point from private key"))
-       .collect();
-
-   // Step 3: Aggregate G2 points
-   let mut aggregated_g2_point = g2_points[0].clone();
-   for g2_point in g2_points.iter().skip(1) {
-       aggregated_g2_point = aggregated_g2_point + g2_point.clone();
+   // Step 3: Register an oracle for each key, proving possession of it
+   let mut oracles = Vec::new();
+   for (private_key, authority) in private_keys.iter().zip(&authorities) {
+       let g2_point_key = G2Point::try_from(private_key).expect("Failed to derive G2 point from private key").0;
+       let (oracle, _) =
+           Pubkey::find_program_address(&[ORACLE_SEED, config.as_ref(), authority.pubkey().as_ref()], &program_id);
+       let register_instruction = Instruction {
+           program_id,
+           accounts: vec![
+               AccountMeta::new(oracle, false),
+               AccountMeta::new(authority.pubkey(), true),
+               AccountMeta::new_readonly(system_program::ID, false),
+               AccountMeta::new_readonly(config, false),
+           ],
+           data: latest_bls::instruction::InitializeOracle {
+               g2_point_key,
+               pop_signature: sign_aggregate(
+                   std::slice::from_ref(private_key),
+                   &proof_of_possession_message(&g2_point_key),
+               ),
+           }
+           .data(),
+       };
+       let blockhash = program.rpc().get_latest_blockhash().expect("Failed to fetch blockhash");
+       let transaction = Transaction::new_signed_with_payer(
+           &[system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), rent), register_instruction],
+           Some(&payer.pubkey()),
+           &[&payer, authority],
+           blockhash,
+       );
+       program.rpc().send_and_confirm_transaction(&transaction).expect("Failed to register oracle");
+       oracles.push(oracle);
    }
-   let aggregated_compressed_pubkey =
-       G2CompressedPoint::try_from(&aggregated_g2_point).expect("Failed to compress G2 point");
 
-   // Step 4: Generate aggregated G1 signature
+   // Step 4: Generate the aggregated G1 signature over the application message
    let message = b"500000.23456".to_vec();
-   let g1_signatures: Vec<G1Point> = private_keys
-       .iter()
-       .map(|key| {
-           key.sign::<Sha256Normalized, &[u8]>(&message)
-               .expect("Failed to sign message with private key")
-       })
-       .collect();
+   let aggregated_and_compressed_g1_signature = sign_aggregate(&private_keys, &application_message(&message));
 
-   let mut aggregated_g1_signature = g1_signatures[0].clone();
-   for g1_signature in g1_signatures.iter().skip(1) {
-       aggregated_g1_signature = aggregated_g1_signature + g1_signature.clone();
-   }
-
-   let aggregated_compressed_signature =
-       G1CompressedPoint::try_from(aggregated_g1_signature).expect("Failed to compress G1 signature");
-
-       let instruction_data = latest_bls::instruction::VerifyAggregatedSignature {
-           aggregated_compressed_pubkey: aggregated_compressed_pubkey.0,
-           aggregated_compressed_signature: aggregated_compressed_signature.0,
+       let instruction_data = latest_bls::instruction::VerifySignature {
+           aggregated_and_compressed_g1_signature,
            message: message.clone(),
        }
        .data();
-   
-       // Step 6: Get blockhash and build transaction
+
+       // Step 5: Get blockhash and build transaction
        let blockhash = program.rpc().get_latest_blockhash().expect("Failed to fetch blockhash");
+       let mut accounts = vec![AccountMeta::new_readonly(config, false)];
+       accounts.extend(oracles.iter().map(|oracle| AccountMeta::new_readonly(*oracle, false)));
        let verify_instruction = Instruction {
            program_id,
-           accounts: vec![], // Add account metas here if required
+           accounts,
            data: instruction_data,
        };
        let transaction = Transaction::new_signed_with_payer(
@@ -96,8 +128,8 @@ let payer = read_keypair_file(keypair_path)
            &[&payer],
            blockhash,
        );
-   
-       // Step 5: Send and confirm the transaction
+
+       // Step 6: Send and confirm the transaction
     match program.rpc().send_and_confirm_transaction(&transaction) {
         Ok(signature) => println!("✅ Signature verified successfully! Transaction: {}", signature),
         Err(error) => {
@@ -107,10 +139,3 @@ let payer = read_keypair_file(keypair_path)
     }
 
 }
-
-//https://explorer.solana.com/tx/2DtkaayJ9d9DmLxhUFpZ761R5XZdzjBNLWaDLLjWBocvoz462CCUaLFdvg6bQ3o8bxqfgfduuFCqCaJjf6EAtHSt?cluster=devnet
-//> Program logged: "Instruction: VerifyAggregatedSignature"
-// > Program consumption: 199313 units remaining
-// > Program consumption: 131281 units remaining
-// > Program logged: "✅ Aggregated signature verified successfully!"
-// > Program consumed: 68848 of 200000 compute units
\ No newline at end of file